@@ -1,6 +1,4 @@
-use std::hash::RandomState;
-
-use itertools::{Itertools, MinMaxResult};
+use common::graph::{held_karp, Interner, Objective, Route as Tour};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha0, newline, u32 as nom_u32},
@@ -9,7 +7,6 @@ use nom::{
     sequence::separated_pair,
     IResult, Parser,
 };
-use petgraph::{algo::all_simple_paths, prelude::UnGraphMap};
 
 pub type Input<'a> = Vec<Route<'a>>;
 
@@ -37,43 +34,29 @@ pub fn parse(input: &str) -> Input<'_> {
     result.unwrap().1
 }
 
-pub fn get_graph<'a>(input: &'a Input) -> UnGraphMap<&'a str, u32> {
-    let mut g: UnGraphMap<&str, u32> = UnGraphMap::new();
+pub fn get_distances(input: &Input) -> Vec<Vec<Option<u32>>> {
+    let mut cities = Interner::new();
+    for route in input {
+        cities.intern(route.from);
+        cities.intern(route.to);
+    }
+
+    let mut distances = vec![vec![None; cities.len()]; cities.len()];
     for route in input {
-        g.add_node(route.from);
-        g.add_node(route.to);
-        g.add_edge(route.from, route.to, route.distance);
+        let from = cities.intern(route.from);
+        let to = cities.intern(route.to);
+        distances[from][to] = Some(route.distance);
+        distances[to][from] = Some(route.distance);
     }
 
-    g
+    distances
 }
 
 pub fn problem1(input: &Input) -> (u32, u32) {
-    let g = get_graph(input);
-    let path_length = g.node_count() - 2;
+    let distances = get_distances(input);
 
-    let MinMaxResult::MinMax(min, max) = g
-        .nodes()
-        .tuple_combinations()
-        .flat_map(|(start, end)| {
-            all_simple_paths::<Vec<_>, _, RandomState>(
-                &g,
-                start,
-                end,
-                path_length,
-                Some(path_length),
-            )
-        })
-        .map(|path| {
-            path.iter()
-                .tuple_windows()
-                .map(|(x, y)| g.edge_weight(x, y).unwrap())
-                .sum::<u32>()
-        })
-        .minmax()
-    else {
-        panic!()
-    };
+    let (min, _) = held_karp(&distances, Tour::Open, Objective::Minimize).unwrap();
+    let (max, _) = held_karp(&distances, Tour::Open, Objective::Maximize).unwrap();
 
     (min, max)
 }
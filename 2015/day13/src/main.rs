@@ -1,7 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use common::{answer, read_input};
-use itertools::Itertools;
+use common::{
+    answer,
+    graph::{held_karp, Interner, Objective, Route},
+    read_input,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -48,25 +51,28 @@ fn parse(input: &str) -> Input<'_> {
 }
 
 fn score_seating(input: &Input) -> i64 {
-    let names: HashSet<&str> = input.keys().flat_map(|(x, y)| vec![*x, *y]).collect();
-    names
-        .iter()
-        .permutations(names.len())
-        .map(|p| {
-            let mut v = p.clone();
-            // add the first to the end so the tuple pairs work out
-            v.extend(vec![p.first().unwrap()]);
+    let mut names = Interner::new();
+    for &(x, y) in input.keys() {
+        names.intern(x);
+        names.intern(y);
+    }
 
-            let happiness: i64 = v
+    // the happiness of sitting next to someone goes both ways
+    let one_way = |x: &str, y: &str| input.get(&(x, y)).copied().unwrap_or(0);
+    let happiness: Vec<Vec<Option<i64>>> = names
+        .labels()
+        .iter()
+        .map(|x| {
+            names
+                .labels()
                 .iter()
-                .tuple_windows()
-                .map(|(&&x, &&y)| input[&(x, y)] + input[&(y, x)])
-                .sum();
-
-            happiness
+                .map(|y| Some(one_way(x, y) + one_way(y, x)))
+                .collect()
         })
-        .max()
-        .unwrap()
+        .collect();
+
+    let (total, _) = held_karp(&happiness, Route::Closed, Objective::Maximize).unwrap();
+    total
 }
 
 fn problem1(input: &Input) -> i64 {
@@ -1,6 +1,7 @@
-use std::ops::Div;
-
-use common::answer;
+use common::{
+    answer,
+    math::primes::{divisor_sums, divisor_sums_limited},
+};
 
 fn main() {
     answer!(problem1(HOUSES));
@@ -9,42 +10,19 @@ fn main() {
 
 const HOUSES: u64 = 33_100_000;
 
-fn divisor_sum<F>(num: u64, f: F) -> u64
-where
-    F: Fn(u64, u64) -> bool,
-{
-    // we only go up to the sqrt for divisors
-    let cap = (num as f64).sqrt() as u64;
-
-    (1..cap).fold(0, |acc, d| {
-        if !num.is_multiple_of(d) {
-            return acc;
-        }
-
-        let d1 = if f(num, d) { d } else { 0 };
-        let d2 = if f(num, num / d) { num / d } else { 0 };
-
-        acc + d1 + d2
-    })
-}
-
-fn find_first_house<F>(houses: u64, f: F) -> u64
-where
-    F: Fn(u64, u64) -> bool,
-{
-    (1..)
-        .find(|&house_number| divisor_sum(house_number, &f) >= houses)
-        .unwrap()
+fn find_first_house(presents: &[u64], target: u64) -> u64 {
+    presents.iter().position(|&p| p >= target).unwrap() as u64
 }
 
 fn problem1(input: u64) -> u64 {
-    let houses = input / 10;
-    find_first_house(houses, |_, _| true)
+    let target = input / 10;
+    // house n always gets at least n lots of presents, so there's no need to look any further than the target
+    find_first_house(&divisor_sums(target as usize), target)
 }
 
 fn problem2(input: u64) -> u64 {
-    let houses = input / 11;
-    find_first_house(houses, |house, d| house.div(d) <= 50)
+    let target = input / 11;
+    find_first_house(&divisor_sums_limited(target as usize, 50), target)
 }
 
 #[cfg(test)]
@@ -41,19 +41,13 @@ struct Disc {
 }
 
 fn problem(input: &Input) -> i64 {
-    let residues: Vec<i64> = input
-        .iter()
-        .enumerate()
-        .map(|(seconds, x)| {
-            // adjust for the falling time + modulus
-            let position_at_t = x.start + (seconds as i64) + 1;
-            x.size - (position_at_t % x.size)
-        })
-        .collect();
+    let congruences = input.iter().enumerate().map(|(seconds, x)| {
+        // adjust for the falling time + modulus
+        let position_at_t = x.start + (seconds as i64) + 1;
+        (x.size - (position_at_t % x.size), x.size)
+    });
 
-    let modulii: Vec<i64> = input.iter().map(|x| x.size).collect();
-
-    chinese_remainder(&residues, &modulii).unwrap()
+    chinese_remainder(congruences).unwrap().0
 }
 
 fn problem1(input: &Input) -> i64 {
@@ -1,4 +1,4 @@
-use common::{answer, read_input};
+use common::{answer, interval::IntervalSet, read_input};
 use nom::{
     character::complete::{char, newline, u32},
     multi::separated_list1,
@@ -10,62 +10,27 @@ fn main() {
     let input = read_input!();
     let input = parse(input);
 
-    answer!(problem1(&input));
-    answer!(problem2(&input));
+    answer!(problem1(&input, u32::MAX));
+    answer!(problem2(&input, u32::MAX));
 }
 
-type Input = Vec<(u32, u32)>;
+type Input = IntervalSet<u32>;
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> =
+    let result: IResult<&str, Vec<(u32, u32)>> =
         separated_list1(newline, separated_pair(u32, char('-'), u32)).parse(input);
 
-    let mut input = result.unwrap().1;
-    input.sort();
-    input
+    result.unwrap().1.into_iter().map(|(a, b)| a..=b).collect()
 }
 
-fn get_contiguous_ranges(ranges: &[(u32, u32)]) -> Vec<(u32, u32)> {
-    ranges.iter().fold(vec![], |mut acc, &r @ (ra, rb)| {
-        // push the first item on the stack
-        match acc.pop() {
-            Some(previous @ (pa, pb)) => {
-                // ranges don't always fully overlap, so we need to make sure contiguous ranges are accounted for
-                if ra <= pb.saturating_add(1) {
-                    acc.push((pa, rb.max(pb)))
-                } else {
-                    acc.push(previous);
-                    acc.push(r);
-                }
-            }
-            None => acc.push(r),
-        };
-
-        acc
-    })
-}
-
-fn problem1(input: &Input) -> u32 {
-    let disallowed = get_contiguous_ranges(&input[..]);
-    // find the first one that doesn't start with 0 and find the first one before the range
-    disallowed
-        .iter()
-        .find_map(|(start, _end)| (*start != 0).then(|| start - 1))
-        .unwrap()
+fn problem1(input: &Input, max: u32) -> u32 {
+    // the lowest IP that isn't covered by any of the blocked ranges
+    input.gaps(0..=max).min().unwrap()
 }
 
-fn problem2(input: &Input) -> u32 {
-    let disallowed = get_contiguous_ranges(&input[..]);
-    // find all the IPs between the ranges. this assumes that input starts with 0 and ends with u32::MAX, which it does
-    disallowed
-        .windows(2)
-        .map(|w| {
-            let (_aa, ab) = w[0];
-            let (ba, _bb) = w[1];
-
-            ba - ab - 1
-        })
-        .sum()
+fn problem2(input: &Input, max: u32) -> u64 {
+    // all the IPs that aren't covered by any of the blocked ranges, which is 2^32 if nothing is blocked
+    input.gaps(0..=max).len().try_into().unwrap()
 }
 
 #[cfg(test)]
@@ -76,7 +41,7 @@ mod test {
     fn first() {
         let input = include_str!("../test.txt");
         let input = parse(input);
-        let result = problem1(&input);
+        let result = problem1(&input, 9);
         assert_eq!(result, 3)
     }
 
@@ -84,7 +49,7 @@ mod test {
     fn second() {
         let input = include_str!("../test.txt");
         let input = parse(input);
-        let result = problem2(&input);
-        assert_eq!(result, 1)
+        let result = problem2(&input, 9);
+        assert_eq!(result, 2)
     }
 }
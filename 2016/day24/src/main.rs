@@ -1,10 +1,8 @@
 use common::{answer, read_input};
-use std::collections::HashMap;
-
-use itertools::Itertools;
 
 use common::{
-    dijkstra::{shortest_path, Edge},
+    dijkstra::{all_pairs, Edge},
+    graph::{held_karp, Objective, Route},
     grid::Grid,
     nom::parse_grid,
 };
@@ -72,34 +70,19 @@ fn problem(m: &Input, back_to_start: bool) -> usize {
         })
         .collect();
 
-    let pairs: HashMap<(u32, u32), usize> = points
-        .iter()
-        .tuple_combinations()
-        .flat_map(|((a, ai), (b, bi))| {
-            let length = shortest_path(&edges, *ai, *bi).unwrap();
-            vec![((*a, *b), length), ((*b, *a), length)]
-        })
-        .collect();
+    let indices: Vec<usize> = points.iter().map(|(_, i)| *i).collect();
+    let distances = all_pairs(&edges, &indices);
 
-    points
-        .iter()
-        .permutations(points.len())
-        .filter(|p| p.first().unwrap().0 == 0)
-        .map(|v| {
-            let travel = v
-                .iter()
-                .tuple_windows()
-                .fold(0, |acc, (a, b)| acc + pairs[&(a.0, b.0)]);
-
-            if back_to_start {
-                let last = v.last().unwrap().0;
-                travel + pairs[&(last, 0)]
-            } else {
-                travel
-            }
-        })
-        .min()
-        .unwrap()
+    // the robot always starts at 0. a closed route visits every point, so it doesn't matter where that one starts
+    let start = points.iter().position(|(n, _)| *n == 0).unwrap();
+    let route = if back_to_start {
+        Route::Closed
+    } else {
+        Route::OpenFrom(start)
+    };
+
+    let (travel, _) = held_karp(&distances, route, Objective::Minimize).unwrap();
+    travel
 }
 
 fn problem1(input: &Input) -> usize {
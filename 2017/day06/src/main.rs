@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use common::{answer, read_input};
+use common::{answer, cycle::brent, read_input};
 use nom::{
     character::complete::{space1, u32},
     multi::separated_list1,
@@ -24,31 +22,29 @@ fn parse(input: &str) -> Input {
     result.unwrap().1
 }
 
-fn problem(input: &Input) -> (u32, u32) {
-    let mut banks = input.clone();
+fn redistribute(banks: &[u32]) -> Vec<u32> {
+    let mut banks = banks.to_vec();
     let len = banks.len();
-    let mut seen: HashMap<Vec<u32>, u32> = HashMap::new();
-
-    for cycle in 0.. {
-        // if we've already seen this bank configuration, return the previous cycle we saw it on
-        if let Some(previous) = seen.insert(banks.clone(), cycle) {
-            return (cycle, cycle - previous);
-        }
 
-        // get the max and its position
-        let &max = banks.iter().max().unwrap();
-        let pos = banks.iter().position(|x| *x == max).unwrap();
+    // get the max and its position
+    let &max = banks.iter().max().unwrap();
+    let pos = banks.iter().position(|x| *x == max).unwrap();
 
-        // clear the current position
-        banks[pos] = 0;
+    // clear the current position
+    banks[pos] = 0;
 
-        // add one to all the other banks in sequence
-        for n in 1..=max {
-            banks[(pos + (n as usize)) % len] += 1;
-        }
+    // add one to all the other banks in sequence
+    for n in 1..=max {
+        banks[(pos + (n as usize)) % len] += 1;
     }
 
-    unreachable!("There *must* be a cycle by definition")
+    banks
+}
+
+fn problem(input: &Input) -> (u32, u32) {
+    // the first repeat happens once we've been all the way around the loop
+    let period = brent(input, |banks| redistribute(banks));
+    ((period.start + period.length) as u32, period.length as u32)
 }
 
 #[cfg(test)]
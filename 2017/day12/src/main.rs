@@ -1,14 +1,7 @@
 use common::dijkstra::{connected_components, Edge};
-use common::nom::usize;
+use common::nom::{adjacency, lines, number};
 use common::{answer, read_input};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{newline, u32},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{preceded, terminated},
-    IResult, Parser,
-};
+use nom::{IResult, Parser};
 
 fn main() {
     let input = read_input!();
@@ -22,15 +15,16 @@ fn main() {
 type Input = Vec<Vec<Edge>>;
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> = separated_list1(
-        newline,
-        preceded(
-            terminated(u32, tag(" <-> ")),
-            separated_list1(tag(", "), map(usize, Edge::new)),
-        ),
-    ).parse(input);
-
-    result.unwrap().1
+    let result: IResult<&str, Vec<(usize, Vec<usize>)>> =
+        lines(adjacency("<->", number)).parse(input);
+
+    // the programs are listed in order, so the position is the id
+    result
+        .unwrap()
+        .1
+        .into_iter()
+        .map(|(_, pipes)| pipes.into_iter().map(Edge::new).collect())
+        .collect()
 }
 
 fn problem(input: &Input) -> (usize, usize) {
@@ -1,12 +1,9 @@
-use common::{answer, read_input};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{char, i64, newline},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{delimited, terminated},
-    IResult, Parser,
+use common::{
+    answer,
+    nom::{labelled_tuple, lines},
+    read_input,
 };
+use nom::{bytes::complete::tag, combinator::map, sequence::terminated, IResult, Parser};
 
 fn main() {
     let input = read_input!();
@@ -43,24 +40,21 @@ impl Particle {
 }
 
 fn parse(input: &str) -> Input {
-    let triple = |s| (terminated(i64, char(',')), terminated(i64, char(',')), i64).parse(s);
-    let particle = |s| {
-        map(
-            (
-                delimited(tag("p=<"), triple, tag(">, ")),
-                delimited(tag("v=<"), triple, tag(">, ")),
-                delimited(tag("a=<"), triple, tag(">")),
-            ),
-            |(p, v, a)| Particle {
-                position: p,
-                velocity: v,
-                acceleration: a,
-            },
-        )
-        .parse(s)
-    };
-
-    let result: IResult<&str, Input> = separated_list1(newline, particle).parse(input);
+    let triple = |label| map(labelled_tuple(label), |[x, y, z]| (x, y, z));
+    let particle = map(
+        (
+            terminated(triple("p"), tag(", ")),
+            terminated(triple("v"), tag(", ")),
+            triple("a"),
+        ),
+        |(p, v, a)| Particle {
+            position: p,
+            velocity: v,
+            acceleration: a,
+        },
+    );
+
+    let result: IResult<&str, Input> = lines(particle).parse(input);
 
     result.unwrap().1
 }
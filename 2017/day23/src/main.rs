@@ -5,7 +5,7 @@ use common::program::{
     registers::{Register, Value},
     Program,
 };
-use common::{answer, read_input};
+use common::{answer, math::is_prime, read_input};
 use nom::{branch::alt, character::complete::newline, multi::separated_list1, IResult, Parser};
 
 fn main() {
@@ -96,19 +96,7 @@ pub fn problem2(input: &str) -> i64 {
 
     println!("Finding composite numbers in [{b}, {c}]");
 
-    let mut composites = 0;
-    for n in (b..=c).step_by(17) {
-        let mut d = 2;
-        while n % d != 0 {
-            d += 1;
-        }
-
-        if n != d {
-            composites += 1;
-        }
-    }
-
-    composites
+    (b..=c).step_by(17).filter(|&n| !is_prime(n as u64)).count() as i64
 }
 
 #[cfg(test)]
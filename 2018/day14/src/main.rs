@@ -1,4 +1,4 @@
-use common::{answer, digits};
+use common::{answer, digits::digits};
 
 fn main() {
     let input = 440231;
@@ -38,7 +38,7 @@ fn problem1(input: usize) -> u64 {
 }
 
 fn problem2(input: usize) -> usize {
-    let check = digits(input);
+    let check: Vec<u8> = digits(input).collect();
     let check_len = check.len();
     let check = &check[..];
     let mut recipes: Vec<u8> = vec![3, 7];
@@ -1,7 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use advent_2018_16::{Instruction, Opcode};
-use common::{answer, nom::usize, read_input};
+use common::{answer, assign::assign, nom::usize, read_input};
 use nom::{
     bytes::complete::tag,
     character::complete::{newline, u32},
@@ -105,45 +105,18 @@ fn problem1((samples, _program): &Input) -> usize {
 }
 
 fn get_mappings(samples: &[InstructionSample]) -> HashMap<usize, Opcode> {
-    // go through and find all the samples that pass
-    let mut opcode_map: HashMap<usize, HashSet<Opcode>> = HashMap::new();
-
-    for x in samples {
-        let matching_opcodes: HashSet<Opcode> = x
+    // every sample narrows down the opcodes its number could be
+    let candidates = samples.iter().map(|x| {
+        let matching_opcodes: Vec<Opcode> = x
             .matching()
             .into_iter()
             .filter_map(|(k, v)| v.then_some(k))
             .collect();
 
-        opcode_map
-            .entry(x.instruction.opcode)
-            .and_modify(|set| {
-                set.extend(matching_opcodes.clone());
-            })
-            .or_insert(matching_opcodes);
-    }
-
-    let mut final_map: HashMap<usize, Opcode> = HashMap::new();
-
-    // reduce everything down to a mapping of one
-    while final_map.len() != 16 {
-        // find the ones that are 1
-        for (key, opcodes) in &opcode_map {
-            if opcodes.len() == 1 {
-                let opcode = *opcodes.iter().next().unwrap();
-                final_map.insert(*key, opcode);
-            }
-        }
-
-        // remove all the solo values from the other potential maps
-        for opcode in final_map.values() {
-            for v in opcode_map.values_mut() {
-                v.remove(opcode);
-            }
-        }
-    }
+        (x.instruction.opcode, matching_opcodes)
+    });
 
-    final_map
+    assign(candidates).expect("opcodes should match up with exactly one number each")
 }
 
 fn problem2((samples, program): &Input) -> usize {
@@ -1,5 +1,5 @@
 use advent_2018_19::ElfCode;
-use common::{answer, read_input};
+use common::{answer, math::sigma, read_input};
 
 fn main() {
     let input = read_input!();
@@ -38,7 +38,7 @@ fn problem2(input: &Input) -> usize {
     // our particular input happens to be in the fourth register
     let x = registers[3];
     // find the sum of all the divisors of the number (this is what the assembly is doing)
-    (1..=x).filter(|n| x.is_multiple_of(*n)).sum()
+    sigma(x as u64).unwrap() as usize
 }
 
 #[cfg(test)]
@@ -1,14 +1,11 @@
-use common::{answer, read_input};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{i64, newline, u64},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{delimited, preceded, separated_pair, terminated},
-    IResult, Parser,
-};
+use std::{cmp::Reverse, collections::BinaryHeap};
 
-use z3::{ast::Int, Optimize, SatResult};
+use common::{
+    answer,
+    nom::{lines, AocParse},
+    read_input,
+};
+use nom::{IResult, Parser};
 
 fn main() {
     let input = read_input!();
@@ -20,6 +17,8 @@ fn main() {
 
 type Input = Vec<Nanobot>;
 
+#[derive(AocParse)]
+#[aoc("pos=<{position}>, r={radius}")]
 struct Nanobot {
     position: (i64, i64, i64),
     radius: u64,
@@ -39,22 +38,7 @@ impl Nanobot {
 }
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> = separated_list1(
-        newline,
-        map(
-            separated_pair(
-                delimited(
-                    tag("pos=<"),
-                    (terminated(i64, tag(",")), terminated(i64, tag(",")), i64),
-                    tag(">"),
-                ),
-                tag(", "),
-                preceded(tag("r="), u64),
-            ),
-            |(position, radius)| Nanobot { position, radius },
-        ),
-    )
-    .parse(input);
+    let result: IResult<&str, Input> = lines(Nanobot::parse).parse(input);
 
     result.unwrap().1
 }
@@ -68,62 +52,105 @@ fn problem1(input: &Input) -> usize {
         .count()
 }
 
-/* This is kind of amazing. I did a binary search that wasn't really working at first, then resorted
- * to cheating with Reddit. A lot of solutions use the Z3 solver to turn this into a SAT problem and then
- * crunch through the problem space. This ran in about 3 min on the input set, which is ...slow...but it works
+/// A cube of points from `min` to `min + size - 1` on every axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Cube {
+    min: (i64, i64, i64),
+    size: i64,
+}
+
+impl Cube {
+    /// The Manhattan distance from `point` to the nearest point in the cube
+    fn distance_to(&self, point: (i64, i64, i64)) -> u64 {
+        let axis = |p: i64, min: i64| {
+            let max = min + self.size - 1;
+            if p < min {
+                min.abs_diff(p)
+            } else if p > max {
+                p.abs_diff(max)
+            } else {
+                0
+            }
+        };
+
+        axis(point.0, self.min.0) + axis(point.1, self.min.1) + axis(point.2, self.min.2)
+    }
+
+    fn in_range(&self, input: &Input) -> usize {
+        input
+            .iter()
+            .filter(|bot| self.distance_to(bot.position) <= bot.radius)
+            .count()
+    }
+
+    fn split(&self) -> impl Iterator<Item = Cube> + '_ {
+        let half = self.size / 2;
+        (0..8).map(move |i| Cube {
+            min: (
+                self.min.0 + half * (i & 1),
+                self.min.1 + half * ((i >> 1) & 1),
+                self.min.2 + half * ((i >> 2) & 1),
+            ),
+            size: half,
+        })
+    }
+}
+
+/* I originally threw the Z3 solver at this, which took about 3 min on the input set, and was painful to build.
+ * This is the octree approach I was fumbling towards with my binary search in the first place:
  *
- * https://cprimozic.net/blog/a-rusty-aoc/#day-23-using-the-z3-smt-solver
+ * https://www.forrestthewoods.com/blog/solving-advent-of-code-in-under-a-second/
  *
- * An alternative would have been an Octree, which looks like what I was initially trying to do with my stupid
- * binary search space algorithm, but I wasn't particularly smart about it.
+ * Start with a cube that covers every bot and the origin, and keep splitting it into eighths. The best point is
+ * always somewhere in there, since pulling it back towards that box keeps it in range of the same bots and doesn't
+ * take it further from the origin.
  *
- * https://www.forrestthewoods.com/blog/solving-advent-of-code-in-under-a-second/
+ * The number of bots in range of a cube is an upper bound for every point inside it, so always splitting the most
+ * promising cube next means the first single point we reach is in range of the most bots, and the closest to the
+ * origin out of those.
 */
 fn problem2(input: &Input) -> u64 {
-    fn abs_diff(a: &Int, b: &Int) -> Int {
-        abs(&(a - b))
+    let coords = input
+        .iter()
+        .flat_map(|bot| [bot.position.0, bot.position.1, bot.position.2])
+        .chain([0]);
+    let lowest = coords.clone().min().unwrap();
+    let highest = coords.max().unwrap();
+
+    let mut size = 1;
+    while lowest + size <= highest {
+        size *= 2;
     }
 
-    fn abs(i: &Int) -> Int {
-        i.gt(0).ite(i, &(i * -1))
+    let start = Cube {
+        min: (lowest, lowest, lowest),
+        size,
+    };
+
+    // most bots first, then closest to the origin
+    let entry = |cube: Cube| {
+        (
+            cube.in_range(input),
+            Reverse(cube.distance_to((0, 0, 0))),
+            cube,
+        )
+    };
+
+    let mut queue = BinaryHeap::from([entry(start)]);
+    while let Some((_, Reverse(distance), cube)) = queue.pop() {
+        if cube.size == 1 {
+            return distance;
+        }
+
+        queue.extend(cube.split().map(entry));
     }
 
-    let zx = Int::new_const("x");
-    let zy = Int::new_const("y");
-    let zz = Int::new_const("z");
-
-    let in_range = input
-        .iter()
-        .map(|bot| {
-            let (x, y, z) = bot.position;
-            let radius = Int::from_u64(bot.radius + 1);
-            let dist_x = abs_diff(&Int::from_i64(x), &zx);
-            let dist_y = abs_diff(&Int::from_i64(y), &zy);
-            let dist_z = abs_diff(&Int::from_i64(z), &zz);
-
-            let distance_to_bot = dist_x + dist_y + dist_z;
-            let is_in_range_of_bot = distance_to_bot.lt(radius);
-
-            is_in_range_of_bot.ite(&Int::from_u64(1), &Int::from_u64(0))
-        })
-        .fold(Int::from_i64(0), |acc, x| acc + x);
-
-    let distance_to_origin = &zx + &zy + &zz;
-
-    let o = Optimize::new();
-    o.maximize(&in_range);
-    o.minimize(&distance_to_origin);
-
-    assert_eq!(o.check(&[]), SatResult::Sat);
-    o.get_model()
-        .and_then(|m| m.eval(&distance_to_origin, true))
-        .and_then(|x| x.as_u64())
-        .unwrap()
+    unreachable!()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{parse, problem1};
+    use crate::{parse, problem1, problem2};
     #[test]
     fn first() {
         let input = include_str!("../test.txt");
@@ -136,7 +163,15 @@ mod test {
     fn second() {
         let input = include_str!("../test2.txt");
         let input = parse(input);
-        let result = crate::problem2(&input);
+        let result = problem2(&input);
         assert_eq!(result, 36);
     }
+
+    #[test]
+    fn second_reaches_origin() {
+        // the bot covers the origin, even though it's well outside the box around the bots
+        let input = parse("pos=<10,10,10>, r=100");
+        let result = problem2(&input);
+        assert_eq!(result, 0);
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use common::{answer, digits, nom::usize, read_input};
+use common::{answer, digits::digits, nom::usize, read_input};
 use nom::{bytes::complete::tag, sequence::separated_pair, IResult, Parser};
 
 fn main() {
@@ -39,7 +39,7 @@ fn has_exact_pair(n: &[u8]) -> bool {
 
 fn problem1(input: &Input) -> usize {
     (input.0..=input.1)
-        .map(digits)
+        .map(|x| digits(x).collect::<Vec<_>>())
         .filter(|x| increasing(x))
         .filter(|x| has_pair(x))
         .count()
@@ -47,7 +47,7 @@ fn problem1(input: &Input) -> usize {
 
 fn problem2(input: &Input) -> usize {
     (input.0..=input.1)
-        .map(digits)
+        .map(|x| digits(x).collect::<Vec<_>>())
         .filter(|x| increasing(x))
         .filter(|x| has_exact_pair(x))
         .count()
@@ -55,7 +55,7 @@ fn problem2(input: &Input) -> usize {
 
 #[cfg(test)]
 mod test {
-    use common::digits;
+    use common::digits::digits;
 
     use crate::{has_exact_pair, parse, problem1, problem2};
     #[test]
@@ -76,8 +76,9 @@ mod test {
 
     #[test]
     fn has_exact_pair_test() {
-        assert!(has_exact_pair(&digits(112233)));
-        assert!(!has_exact_pair(&digits(123444)));
-        assert!(has_exact_pair(&digits(111122)));
+        let d = |n: usize| digits(n).collect::<Vec<_>>();
+        assert!(has_exact_pair(&d(112233)));
+        assert!(!has_exact_pair(&d(123444)));
+        assert!(has_exact_pair(&d(111122)));
     }
 }
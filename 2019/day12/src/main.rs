@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use common::{answer, math::lcm, read_input};
+use common::{answer, math::lcm_all, read_input};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
@@ -203,7 +203,7 @@ fn problem2(input: &Input) -> i64 {
         }
     }
 
-    lcm(count_x.unwrap(), lcm(count_y.unwrap(), count_z.unwrap()))
+    lcm_all([count_x, count_y, count_z].map(Option::unwrap)).unwrap()
 }
 
 #[cfg(test)]
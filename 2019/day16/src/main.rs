@@ -1,4 +1,4 @@
-use common::{answer, nom::single_digit, read_input};
+use common::{answer, digits::from_digits, nom::single_digit, read_input};
 use nom::{multi::many1, IResult, Parser};
 
 fn main() {
@@ -48,21 +48,17 @@ fn fft(input: &[u32]) -> Vec<u32> {
         .collect()
 }
 
-fn to_number(value: &[u32]) -> u32 {
-    value.iter().fold(0, |acc, x| (acc * 10) + x)
-}
-
 fn problem1(input: &Input) -> u32 {
     let mut value = input.clone();
     for _n in 0..100 {
         value = fft(&value);
     }
 
-    to_number(&value[0..8])
+    from_digits(value[0..8].iter().copied()).unwrap()
 }
 
 fn problem2(input: &Input) -> u32 {
-    let offset = to_number(&input[0..7]) as usize;
+    let offset: usize = from_digits(input[0..7].iter().copied()).unwrap();
     let mut value: Vec<u32> = input.repeat(10_000).iter().skip(offset).copied().collect();
 
     for _n in 0..100 {
@@ -72,7 +68,7 @@ fn problem2(input: &Input) -> u32 {
         });
     }
 
-    to_number(&value[0..8])
+    from_digits(value[0..8].iter().copied()).unwrap()
 }
 
 #[cfg(test)]
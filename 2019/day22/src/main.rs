@@ -1,10 +1,8 @@
-use std::collections::VecDeque;
-
-use common::{answer, read_input};
+use common::{answer, math::Affine, read_input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{i128, newline},
+    character::complete::{i64, newline},
     combinator::map,
     multi::separated_list1,
     sequence::preceded,
@@ -23,8 +21,8 @@ type Input = Vec<Technique>;
 
 enum Technique {
     DealNewStack,
-    Cut(i128),
-    DealWithIncrement(i128),
+    Cut(i64),
+    DealWithIncrement(i64),
 }
 
 fn parse(input: &str) -> Input {
@@ -33,156 +31,52 @@ fn parse(input: &str) -> Input {
         alt((
             map(tag("deal into new stack"), |_| Technique::DealNewStack),
             map(
-                preceded(tag("deal with increment "), i128),
+                preceded(tag("deal with increment "), i64),
                 Technique::DealWithIncrement,
             ),
-            map(preceded(tag("cut "), i128), Technique::Cut),
+            map(preceded(tag("cut "), i64), Technique::Cut),
         )),
     ).parse(input);
 
     result.unwrap().1
 }
 
-fn shuffle(input: &Input, deck: &[usize]) -> Vec<usize> {
-    let mut deck = deck.to_vec();
-    let card_count = deck.len();
-
-    for technique in input {
-        match technique {
-            Technique::DealNewStack => deck.reverse(),
-            Technique::Cut(x) => {
-                let top = if x.is_positive() {
-                    *x as usize
-                } else {
-                    card_count - (x.unsigned_abs() as usize)
-                };
-
-                deck = deck
-                    .iter()
-                    .cycle()
-                    .skip(top)
-                    .take(card_count)
-                    .copied()
-                    .collect();
-            }
-            Technique::DealWithIncrement(x) => {
-                let mut current = 0;
-                let mut hand: VecDeque<usize> = deck.iter().copied().collect();
-
-                while let Some(card) = hand.pop_front() {
-                    deck[current] = card;
-                    current = (current + (*x as usize)) % card_count;
-                }
-            }
-        }
-    }
-
-    deck
+/// Where each technique sends the card at position x, all rolled into one map. Cutting n cards moves everything
+/// down by n, dealing into a new stack flips the deck over, and dealing with an increment spreads the cards out
+/// by that much.
+fn compile(input: &Input, cards: i64) -> Affine<i64> {
+    input
+        .iter()
+        .map(|technique| match *technique {
+            Technique::DealNewStack => Affine::new(-1, -1, cards),
+            Technique::Cut(n) => Affine::new(1, -n, cards),
+            Technique::DealWithIncrement(n) => Affine::new(n, 0, cards),
+        })
+        .fold(Affine::identity(cards), |acc, step| acc.then(&step))
 }
 
-fn problem1(input: &Input) -> usize {
-    let deck: Vec<usize> = (0..10_007).collect();
-    let result = shuffle(input, &deck);
-
-    result.iter().position(|x| *x == 2019).unwrap()
+fn problem1(input: &Input) -> i64 {
+    compile(input, 10_007).apply(2019)
 }
 
-fn problem2(input: &Input) -> i128 {
-    // I super cheated on this. No way I was ever getting this myself
-    const CARDS: i128 = 119_315_717_514_047;
-    const ITERATIONS: i128 = 101_741_582_076_661;
-
-    let mut a = 1;
-    let mut b = 0;
-
-    for op in input.iter().rev() {
-        match op {
-            &Technique::Cut(n) => {
-                b += if n < 0 { n + CARDS } else { n };
-            }
-            &Technique::DealWithIncrement(n) => {
-                let inv = modinv(n, CARDS);
-                a *= inv % CARDS;
-                b *= inv % CARDS;
-            }
-            Technique::DealNewStack => {
-                b = -(b + 1);
-                a *= -1;
-            }
-        }
-
-        a %= CARDS;
-        b %= CARDS;
-
-        if a < 0 {
-            a += CARDS;
-        }
-
-        if b < 0 {
-            b += CARDS;
-        }
-    }
-
-    let x = modp(a, ITERATIONS, CARDS);
-    let i1 = x * 2020 % CARDS;
-    let i2 = (x + CARDS - 1) % CARDS;
-    let i3 = b * i2 % CARDS;
-    let i4 = modp(a - 1, CARDS - 2, CARDS);
-
-    (i1 + i3 * i4) % CARDS
-}
-
-fn modinv(mut a: i128, mut base: i128) -> i128 {
-    if base == 1 {
-        return 0;
-    }
-
-    let orig = base;
-
-    let mut x = 1;
-    let mut y = 0;
-
-    while a > 1 {
-        let q = a / base;
-        let tmp = base;
-        base = a % base;
-        a = tmp;
-        let tmp = y;
-        y = x - q * y;
-        x = tmp;
-    }
-
-    if x < 0 {
-        x + orig
-    } else {
-        x
-    }
-}
-
-fn modp(b: i128, exp: i128, base: i128) -> i128 {
-    let mut x = 1;
-    let mut p = b % base;
-
-    for i in 0..128 {
-        if 1 & (exp >> i) == 1 {
-            x = x * p % base;
-        }
-
-        p = p * p % base;
-    }
+fn problem2(input: &Input) -> i64 {
+    const CARDS: i64 = 119_315_717_514_047;
+    const ITERATIONS: i64 = 101_741_582_076_661;
 
-    x
+    // run the whole thing backwards to find which card ends up at 2020
+    let shuffle = compile(input, CARDS).pow(ITERATIONS).unwrap();
+    shuffle.inverse().unwrap().apply(2020)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{parse, problem2, shuffle};
+    use crate::{compile, parse, problem2};
     #[test]
     fn small_deck() {
         let input = include_str!("../test.txt");
         let input = parse(input);
-        let deck: Vec<usize> = (0..10).collect();
-        let result = shuffle(&input, &deck);
+        let position = compile(&input, 10).inverse().unwrap();
+        let result: Vec<i64> = (0..10).map(|x| position.apply(x)).collect();
         assert_eq!(result, vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]);
     }
 
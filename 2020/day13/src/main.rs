@@ -43,16 +43,13 @@ fn problem1(input: &Input) -> u32 {
 
 fn problem2(input: &Input) -> i64 {
     let (_timestamp, busses) = input;
-    let pairs: Vec<(i64, i64)> = busses
+    // bus b leaves i minutes after the timestamp, so the timestamp is -i mod b
+    let congruences = busses
         .iter()
         .enumerate()
-        .filter_map(|(i, b)| b.map(|b| (b as i64, b as i64 - i as i64)))
-        .collect();
+        .filter_map(|(i, b)| b.map(|b| (-(i as i64), b as i64)));
 
-    let busses: Vec<i64> = pairs.iter().map(|x| x.0).collect();
-    let offsets: Vec<i64> = pairs.iter().map(|x| x.1).collect();
-
-    chinese_remainder(&offsets, &busses).unwrap()
+    chinese_remainder(congruences).unwrap().0
 }
 
 #[cfg(test)]
@@ -1,10 +1,6 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    fmt::Display,
-    ops::RangeInclusive,
-};
+use std::{fmt::Display, ops::RangeInclusive};
 
-use common::{answer, read_input};
+use common::{answer, assign::assign, read_input};
 use nom::{
     bytes::complete::{tag, take_until},
     character::complete::{newline, u64},
@@ -120,28 +116,15 @@ fn problem2(input: &Input) -> u64 {
         .filter(|ticket| ticket.is_valid(&input.rules))
         .collect();
 
-    let mut possibles: Vec<(String, BTreeSet<usize>)> = input
-        .rules
-        .iter()
-        .map(|rule| {
-            let possible_columns = (0..input.rules.len())
-                .filter(|i| valid_tickets.iter().all(|t| rule.is_valid(t.0[*i])))
-                .collect();
-            (rule.name.to_string(), possible_columns)
-        })
-        .collect();
-
-    // sort by least matches
-    possibles.sort_by_key(|x| x.1.len());
+    // each rule can go in any column where every ticket is valid for it
+    let candidates = input.rules.iter().map(|rule| {
+        let possible_columns: Vec<usize> = (0..input.rules.len())
+            .filter(|i| valid_tickets.iter().all(|t| rule.is_valid(t.0[*i])))
+            .collect();
+        (rule.name.as_str(), possible_columns)
+    });
 
-    // pull off each of the matched columns and associate them with a name
-    let mut matches: BTreeMap<String, usize> = BTreeMap::new();
-    for (name, possible) in &possibles {
-        let already_matched = matches.values().copied().collect();
-        if let Some(unmatched) = possible.difference(&already_matched).next() {
-            matches.insert(name.to_string(), *unmatched);
-        };
-    }
+    let matches = assign(candidates).expect("rules should match up with exactly one column each");
 
     // multiply all the fields that start with product
     matches
@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use common::{answer, read_input};
+use common::{answer, assign::assign, read_input};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, newline},
@@ -80,25 +80,11 @@ fn problem1(input: &Input) -> usize {
 }
 
 fn problem2(input: &Input) -> String {
-    let mut all_allergens = create_allergen_map(input);
-    let mut dangerous: BTreeMap<&str, &str> = BTreeMap::new();
-
-    while !all_allergens.is_empty() {
-        let known: Vec<(&str, &str)> = all_allergens
-            .iter()
-            .filter_map(|(k, v)| (v.len() == 1).then_some((*k, *v.first().unwrap())))
-            .collect();
-
-        for (ingredient, allergen) in known {
-            dangerous.insert(ingredient, allergen);
-            all_allergens.remove(ingredient);
-
-            for v in all_allergens.values_mut() {
-                v.remove(allergen);
-            }
-        }
-    }
+    let dangerous = assign(create_allergen_map(input))
+        .expect("allergens should match up with exactly one ingredient each");
 
+    // sorted by allergen
+    let dangerous: BTreeMap<&str, &str> = dangerous.into_iter().collect();
     let dangerous: Vec<String> = dangerous.values().map(|x| x.to_string()).collect();
     dangerous.join(",")
 }
@@ -1,4 +1,9 @@
-use common::{answer, digits, nom::usize, read_input, to_number};
+use common::{
+    answer,
+    digits::{digits, from_digits},
+    nom::usize,
+    read_input,
+};
 use nom::{combinator::map, IResult, Parser};
 
 fn main() {
@@ -12,9 +17,8 @@ fn main() {
 type Input = Vec<usize>;
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> = map(map(usize, digits), |x| {
-        x.iter().map(|x| *x as usize).collect()
-    }).parse(input);
+    let result: IResult<&str, Input> =
+        map(usize, |x| digits(x).map(|d| d as usize).collect()).parse(input);
 
     result.unwrap().1
 }
@@ -75,7 +79,7 @@ fn problem1(input: &Input) -> u32 {
         }
     }
 
-    to_number(&answer)
+    from_digits(answer).unwrap()
 }
 
 fn problem2(input: &Input) -> usize {
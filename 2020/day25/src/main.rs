@@ -21,7 +21,7 @@ fn parse(input: &str) -> Input {
 }
 
 fn transform(subject: u64, loop_size: u64) -> u64 {
-    mod_pow(subject, loop_size, 20201227)
+    mod_pow(subject, loop_size, 20201227).unwrap()
 }
 
 fn find_loop_size(key: u64) -> u64 {
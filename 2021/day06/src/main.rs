@@ -1,5 +1,5 @@
-use common::nom::usize;
-use nom::{bytes::complete::tag, multi::separated_list1, IResult, Parser};
+use common::nom::numbers;
+use nom::IResult;
 
 use common::{answer, read_input};
 
@@ -17,7 +17,7 @@ fn main() {
 }
 
 fn parse(input: &str) -> Vec<usize> {
-    let result: IResult<&str, Vec<usize>> = separated_list1(tag(","), usize).parse(input);
+    let result: IResult<&str, Vec<usize>> = numbers(input);
 
     result.unwrap().1
 }
@@ -1,15 +1,13 @@
-use counter::Counter;
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{i64, newline},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{delimited, preceded, separated_pair},
-    IResult, Parser,
+    branch::alt, bytes::complete::tag, combinator::map, sequence::terminated, IResult, Parser,
 };
 
-use common::{answer, read_input};
+use common::{
+    answer,
+    geometry::{Aabb, Region},
+    nom::{labelled_range, lines},
+    read_input,
+};
 
 fn main() {
     let input = read_input!();
@@ -22,22 +20,22 @@ fn main() {
 type Input = Vec<Step>;
 
 fn parse(input: &str) -> Input {
-    let range = |s| separated_pair(i64, tag(".."), i64).parse(s);
-    let result: IResult<&str, Input> = separated_list1(
-        newline,
-        map(
-            (
-                alt((map(tag("on "), |_| true), map(tag("off "), |_| false))),
-                delimited(tag("x="), range, tag(",")),
-                delimited(tag("y="), range, tag(",")),
-                preceded(tag("z="), range),
-            ),
-            |(on, x, y, z)| Step {
-                on,
-                cube: Cube { x, y, z },
-            },
+    let result: IResult<&str, Input> = lines(map(
+        (
+            alt((map(tag("on "), |_| true), map(tag("off "), |_| false))),
+            terminated(labelled_range("x"), tag(",")),
+            terminated(labelled_range("y"), tag(",")),
+            labelled_range("z"),
         ),
-    ).parse(input);
+        |(on, x, y, z)| Step {
+            on,
+            cube: Aabb::new(
+                [*x.start(), *y.start(), *z.start()],
+                [*x.end(), *y.end(), *z.end()],
+            ),
+        },
+    ))
+    .parse(input);
 
     result.unwrap().1
 }
@@ -45,68 +43,24 @@ fn parse(input: &str) -> Input {
 #[derive(Clone, Copy, Debug)]
 struct Step {
     on: bool,
-    cube: Cube,
-}
-
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Cube {
-    x: (i64, i64),
-    y: (i64, i64),
-    z: (i64, i64),
-}
-
-impl Cube {
-    fn intersect(&self, other: &Cube) -> Option<Cube> {
-        let c = Cube {
-            x: (self.x.0.max(other.x.0), self.x.1.min(other.x.1)),
-            y: (self.y.0.max(other.y.0), self.y.1.min(other.y.1)),
-            z: (self.z.0.max(other.z.0), self.z.1.min(other.z.1)),
-        };
-
-        (c.x.0 <= c.x.1 && c.y.0 <= c.y.1 && c.z.0 <= c.z.1).then_some(c)
-    }
-
-    fn volume(&self) -> i64 {
-        (self.x.1 - self.x.0 + 1) * (self.y.1 - self.y.0 + 1) * (self.z.1 - self.z.0 + 1)
-    }
+    cube: Aabb<3>,
 }
 
-// this is just an application of https://en.wikipedia.org/wiki/Inclusion–exclusion_principle
 fn count_cubes(steps: &[Step]) -> i64 {
-    let cubes = steps.iter().fold(Counter::new(), |cubes, step| {
-        let mut update: Counter<Cube, i64> = Counter::new();
-
-        // first include this cube
-        if step.on {
-            update.insert(step.cube, 1);
-        }
-
-        let intersections = cubes
-            .iter()
-            .filter_map(|(c, sign)| step.cube.intersect(c).map(|i| (i, *sign)));
-
-        // now exclude it from the counts of all other cubes that it's intersecting
-        for (i, sign) in intersections {
-            let x = update.entry(i).or_insert(0);
-            *x -= sign;
-        }
-
-        cubes + update
-    });
+    let mut region = Region::new();
+    for step in steps {
+        region.switch(step.cube, step.on);
+    }
 
-    cubes.iter().map(|(cube, sign)| cube.volume() * sign).sum()
+    region.volume() as i64
 }
 
 fn problem1(input: &Input) -> i64 {
-    let init_region = Cube {
-        x: (-50, 50),
-        y: (-50, 50),
-        z: (-50, 50),
-    };
+    let init_region = Aabb::new([-50; 3], [50; 3]);
 
     let in_init_region: Vec<Step> = input
         .iter()
-        .filter(|x| x.cube.intersect(&init_region).is_some())
+        .filter(|x| x.cube.intersects(&init_region))
         .copied()
         .collect();
 
@@ -1,10 +1,8 @@
 use common::{answer, read_input};
 use common::dijkstra::{shortest_path, Edge};
 use common::grid::Grid;
-use common::nom::parse_grid;
-use nom::{
-    branch::alt, bytes::complete::tag, character::complete::none_of, combinator::map, IResult, Parser,
-};
+use common::nom::{parse_grid_with_markers, Marker, Markers};
+use nom::{character::complete::none_of, IResult, Parser};
 
 fn main() {
     let input = read_input!();
@@ -14,93 +12,58 @@ fn main() {
     answer!(problem2(&input));
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Position {
-    Start,
-    End,
-    Normal(char),
-}
-
-impl From<&Position> for u32 {
-    fn from(val: &Position) -> Self {
-        (match val {
-            Position::Start => 'a',
-            Position::End => 'z',
-            Position::Normal(c) => *c,
-        }) as u32
-    }
-}
-
-impl Position {
-    fn can_travel_to(&self, dest: &Position) -> bool {
-        let start_height: u32 = self.into();
-        let dest_height: u32 = dest.into();
-
-        dest_height <= start_height + 1
-    }
-
-    fn is_potential_start(&self) -> bool {
-        matches!(self, Position::Start | Position::Normal('a'))
-    }
+fn can_travel_to(start: char, dest: char) -> bool {
+    dest as u32 <= start as u32 + 1
 }
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Grid<Position>> = parse_grid(alt((
-        map(tag("S"), |_| Position::Start),
-        map(tag("E"), |_| Position::End),
-        map(none_of("\n"), Position::Normal),
-    ))).parse(input);
-
-    result.unwrap().1
+    // the start and end are at the lowest and highest elevations
+    let result: IResult<&str, (Grid<char>, Markers)> = parse_grid_with_markers(
+        [Marker::unique('S', 'a'), Marker::unique('E', 'z')],
+        none_of("\n"),
+    )
+    .parse(input);
+
+    let (map, markers) = result.unwrap().1;
+    let start = map.get_grid_index(markers.get('S'));
+    let finish = map.get_grid_index(markers.get('E'));
+    Input { map, start, finish }
 }
 
-type Input = Grid<Position>;
+struct Input {
+    map: Grid<char>,
+    start: usize,
+    finish: usize,
+}
 
-fn get_edges(map: &Grid<Position>) -> Vec<Vec<Edge>> {
+fn get_edges(map: &Grid<char>) -> Vec<Vec<Edge>> {
     map.iter()
         .map(|square| {
             square
                 .neighbors()
                 .iter()
-                .filter(|&neighbor| square.data.can_travel_to(neighbor.data))
+                .filter(|&neighbor| can_travel_to(*square.data, *neighbor.data))
                 .map(|neighbor| Edge::from_map_square(neighbor))
                 .collect()
         })
         .collect()
 }
 
-fn problem1(map: &Input) -> usize {
-    let mut start: usize = 0;
-    let mut finish: usize = 0;
-
-    // find both the start and finish squares
-    for square in map.iter() {
-        match square.data {
-            Position::Start => start = square.get_grid_index(),
-            Position::End => finish = square.get_grid_index(),
-            Position::Normal(_) => {}
-        }
-    }
-    let edges = get_edges(map);
-    shortest_path(&edges, start, finish).unwrap()
+fn problem1(input: &Input) -> usize {
+    let edges = get_edges(&input.map);
+    shortest_path(&edges, input.start, input.finish).unwrap()
 }
 
-fn problem2(map: &Input) -> usize {
-    // find the only finish square
-    let mut finish: usize = 0;
-    for square in map.iter() {
-        if let Position::End = square.data {
-            finish = square.get_grid_index()
-        }
-    }
-
-    let edges = get_edges(map);
+fn problem2(input: &Input) -> usize {
+    let edges = get_edges(&input.map);
 
-    map.iter()
+    input
+        .map
+        .iter()
         // only take the potential starting locations
-        .filter(|s| s.data.is_potential_start())
+        .filter(|s| *s.data == 'a')
         // find the shortest paths from a to z
-        .filter_map(|start| shortest_path(&edges, start.get_grid_index(), finish))
+        .filter_map(|start| shortest_path(&edges, start.get_grid_index(), input.finish))
         // get the shortest
         .min()
         .unwrap()
@@ -1,13 +1,12 @@
 use common::{answer, read_input};
-use common::extensions::PointExt;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{i64 as nom_i64, newline},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult, Parser,
+use common::{
+    extensions::PointExt,
+    interval::IntervalSet,
+    nom::{lines, AocParse},
 };
+use nom::{IResult, Parser};
 use rayon::prelude::*;
+use std::ops::RangeInclusive;
 
 fn main() {
     let input = read_input!();
@@ -23,50 +22,42 @@ struct Input {
 }
 
 impl Input {
-    fn get_coverages(&self, row: i64) -> Vec<(i64, i64)> {
-        let mut coverages: Vec<(i64, i64)> = self
+    fn get_coverages(&self, row: i64) -> IntervalSet<i64> {
+        let coverages: Vec<RangeInclusive<i64>> = self
             .sensors
             .par_iter()
             .filter_map(|s| s.get_coverage(row))
             .collect();
 
-        coverages.sort();
-        coverages
+        coverages.into_iter().collect()
     }
 }
 
-fn parse_coord(input: &str) -> IResult<&str, Point> {
-    separated_pair(
-        preceded(tag("x="), nom_i64),
-        tag(", "),
-        preceded(tag("y="), nom_i64),
-    ).parse(input)
+#[derive(AocParse)]
+#[aoc("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")]
+struct Report {
+    sx: i64,
+    sy: i64,
+    bx: i64,
+    by: i64,
 }
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Vec<(Point, Point)>> = separated_list1(
-        newline,
-        preceded(
-            tag("Sensor at "),
-            separated_pair(parse_coord, tag(": closest beacon is at "), parse_coord),
-        ),
-    ).parse(input);
-
-    let pairs = result.unwrap().1;
-    let sensors = pairs
+    let result: IResult<&str, Vec<Report>> = lines(Report::parse).parse(input);
+
+    let reports = result.unwrap().1;
+    let sensors = reports
         .iter()
-        .map(|&(s @ (sx, sy), b)| Sensor {
+        .map(|&Report { sx, sy, bx, by }| Sensor {
             x: sx,
             y: sy,
-            dist: s.manhattan(&b),
+            dist: (sx, sy).manhattan(&(bx, by)),
         })
         .collect();
 
     Input { sensors }
 }
 
-type Point = (i64, i64);
-
 #[derive(Debug)]
 struct Sensor {
     x: i64,
@@ -75,7 +66,7 @@ struct Sensor {
 }
 
 impl Sensor {
-    fn get_coverage(&self, row: i64) -> Option<(i64, i64)> {
+    fn get_coverage(&self, row: i64) -> Option<RangeInclusive<i64>> {
         let &Sensor { x, y, dist } = self;
         // only consider the rows within manhattan distance of this sensor
         let y_in_range = y - dist <= row && row <= y + dist;
@@ -89,39 +80,16 @@ impl Sensor {
             let start_x = x - (remaining_x_distance as i64);
             let end_x = x + (remaining_x_distance as i64);
 
-            (start_x, end_x)
+            start_x..=end_x
         })
     }
 }
 
-fn get_contiguous_ranges(ranges: &[(i64, i64)]) -> Vec<(i64, i64)> {
-    // there is likely a better representation for this than a vec, but the
-    // acc will only ever have 0, 1, or 2 ranges
-    ranges.iter().fold(vec![], |mut acc, &r @ (ra, rb)| {
-        // push the first item on the stack
-        match acc.pop() {
-            Some(previous @ (pa, pb)) => {
-                if ra <= pb {
-                    acc.push((pa, rb.max(pb)))
-                } else {
-                    acc.push(previous);
-                    acc.push(r);
-                }
-            }
-            None => acc.push(r),
-        };
-
-        acc
-    })
-}
-
 fn problem1(input: &Input, row: i64) -> i64 {
     let coverages = input.get_coverages(row);
 
-    // smash the ranges together and get the sum of the distances between them
-    let &(start, end) = get_contiguous_ranges(&coverages).first().unwrap();
-
-    start.abs_diff(end) as i64
+    // the ranges are already smashed together, but one of the covered spots is the beacon on this row
+    coverages.len() as i64 - 1
 }
 
 /* I completely rewrote the implementation between problem 1 and 2. My initial
@@ -138,12 +106,9 @@ fn problem2(input: &Input, max_search_area: i64) -> i64 {
             // find the coverages on this particular row
             let coverages = input.get_coverages(row);
 
-            let ranges = get_contiguous_ranges(&coverages);
-            (ranges.len() > 1).then(|| {
-                // the hole in between these two ranges is the x value of the beacon
-                let x = ranges[0].1 + 1;
-                (x, row)
-            })
+            // the only hole in the search area is the x value of the beacon
+            let x = coverages.gaps(0..=max_search_area).min()?;
+            Some((x, row))
         })
         .unwrap();
 
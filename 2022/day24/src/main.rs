@@ -76,7 +76,7 @@ impl Valley {
         let width = input.first().unwrap().len() as i64;
 
         // the blizzards are periodic, so we only need to generate up to a certain size
-        let cycle = lcm(height, width);
+        let cycle = lcm(height, width).unwrap();
 
         let mut points: BTreeSet<Point> = BTreeSet::new();
         // we're going to create a 3D map where the third dimension is time
@@ -1,6 +1,6 @@
-use std::{collections::VecDeque, ops::Range};
+use std::ops::{Range, RangeInclusive};
 
-use common::{answer, read_input};
+use common::{answer, interval::IntervalSet, read_input};
 use nom::{
     bytes::complete::{tag, take_until},
     character::complete::{newline, u64},
@@ -62,24 +62,20 @@ impl AlmanacRange {
         }
     }
 
-    fn get_overlap(&self, r: &Range<u64>) -> Option<Range<u64>> {
-        let overlap_start = r.start.max(self.source_start);
-        let overlap_end = r.end.min(self.source_start + self.length);
-
-        let overlap = overlap_start..overlap_end;
-        (!overlap.is_empty()).then_some(overlap)
+    fn source(&self) -> Range<u64> {
+        self.source_start..self.source_start + self.length
     }
 
-    fn create_mapped_range(&self, overlap: Range<u64>) -> Range<u64> {
+    fn create_mapped_range(&self, overlap: RangeInclusive<u64>) -> RangeInclusive<u64> {
         // Get the offsets
-        let start_offset = overlap.start - self.source_start;
-        let end_offset = overlap.end - self.source_start;
+        let start_offset = overlap.start() - self.source_start;
+        let end_offset = overlap.end() - self.source_start;
 
         // Map the range
         let new_start = self.destination_start + start_offset;
         let new_end = self.destination_start + end_offset;
 
-        new_start..new_end
+        new_start..=new_end
     }
 }
 
@@ -89,52 +85,29 @@ struct AlmanacMap {
 }
 
 impl AlmanacMap {
-    fn process(&self, seeds: Vec<Range<u64>>) -> Vec<Range<u64>> {
-        let mut new_seeds = vec![];
-
-        /* I would normally write this up as a fold, but we have to consider partial range overlaps, which means
-        we'll need to break the AlmanacRange in two and then re-consider the remainder of the range. Consider the
-        situation where a seed range overlaps 2 ranges:
+    fn process(&self, seeds: &IntervalSet<u64>) -> IntervalSet<u64> {
+        /* A seed range can overlap several almanac ranges, and only partially overlap some of them:
 
         Seed Range:                       | ---- sr1 ---- |
         Almanac Ranges:        | ---- ar1 ---- |    | ---- ar2 ----- |
 
-        We'll have to break sr1 into three chunks. Rather than having a huge else if, it's easier to just consider
-        each overlapping segment and then push back the remainders onto the VecDeque. This would get worse if we
-        have an interior almanac range between ar1 and ar2 (which does happen in the real input).
+        Each almanac range maps whatever part of the seeds it overlaps, and whatever no almanac range touched is a
+        passthrough.
         */
-        let mut seed_queue = VecDeque::from(seeds);
-        while let Some(range) = seed_queue.pop_front() {
-            let mapped: Vec<Range<u64>> = self
-                .ranges
-                .iter()
-                .filter_map(|almanac_range| {
-                    almanac_range.get_overlap(&range).map(|overlap| {
-                        // if we're not fully overlapped, then we need to push partial ranges back to consider again
-                        if overlap.start > range.start {
-                            seed_queue.push_back(range.start..overlap.start)
-                        }
-
-                        if overlap.end < range.end {
-                            seed_queue.push_back(overlap.end..range.end)
-                        }
-
-                        // map the overlapped part of the range
-                        almanac_range.create_mapped_range(overlap)
-                    })
-                })
-                .collect();
-
-            if mapped.is_empty() {
-                // nothing got mapped, so this range is a passthrough
-                new_seeds.push(range);
-            } else {
-                // push the mapped ranges
-                new_seeds.extend(mapped);
-            }
+        let mut mapped = IntervalSet::new();
+        let mut passthrough = seeds.clone();
+        for almanac_range in &self.ranges {
+            let source = IntervalSet::from(almanac_range.source());
+            mapped.extend(
+                seeds
+                    .intersection(&source)
+                    .iter()
+                    .map(|overlap| almanac_range.create_mapped_range(overlap)),
+            );
+            passthrough.remove(almanac_range.source());
         }
 
-        new_seeds
+        mapped.union(&passthrough)
     }
 }
 
@@ -145,9 +118,9 @@ struct Almanac {
 }
 
 impl Almanac {
-    fn get_mapped_seeds(&self, seed_ranges: Vec<Range<u64>>) -> u64 {
-        let mapped = self.maps.iter().fold(seed_ranges, |acc, m| m.process(acc));
-        mapped.iter().map(|x| x.start).min().unwrap()
+    fn get_mapped_seeds(&self, seed_ranges: IntervalSet<u64>) -> u64 {
+        let mapped = self.maps.iter().fold(seed_ranges, |acc, m| m.process(&acc));
+        mapped.min().unwrap()
     }
 }
 
@@ -120,7 +120,7 @@ impl<'a> Ghost<'a> {
 }
 
 fn problem2(input: &Input) -> i64 {
-    input
+    let cycle_times = input
         .room_map
         .keys()
         .filter_map(|x| {
@@ -131,10 +131,10 @@ fn problem2(input: &Input) -> i64 {
             })
         })
         // have each of them find their cycle time
-        .map(|mut x| x.get_cycle_time() as i64)
-        // now get the least common multiple of all the different cycle times
-        .reduce(common::math::lcm)
-        .unwrap()
+        .map(|mut x| x.get_cycle_time() as i64);
+
+    // now get the least common multiple of all the different cycle times
+    common::math::lcm_all(cycle_times).unwrap()
 }
 
 #[cfg(test)]
@@ -1,6 +1,4 @@
-use cached::{Cached, UnboundCache};
-
-use common::{answer, nom::usize, read_input};
+use common::{answer, memo::Memo, nom::usize, read_input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -77,19 +75,25 @@ impl SpringRecord {
     }
 }
 
-type SolutionCache = UnboundCache<(usize, usize, Option<usize>), usize>;
+type SolutionCache = Memo<(usize, usize, Option<usize>), usize>;
 fn count_solutions(
     cache: &mut SolutionCache,
     springs: &[Spring],
     groups: &[usize],
     current_run: Option<usize>,
 ) -> usize {
-    use Spring::*;
     // hit the cache first to see if we have a solution already
     let key = (springs.len(), groups.len(), current_run);
-    if let Some(x) = cache.cache_get(&key) {
-        return *x;
-    }
+    cache.get_or_compute(key, |cache| solve(cache, springs, groups, current_run))
+}
+
+fn solve(
+    cache: &mut SolutionCache,
+    springs: &[Spring],
+    groups: &[usize],
+    current_run: Option<usize>,
+) -> usize {
+    use Spring::*;
 
     // these are all the terminating conditions
     match (springs.len(), groups.len(), current_run) {
@@ -143,8 +147,6 @@ fn count_solutions(
         }
     };
 
-    cache.cache_set(key, possible);
-
     possible
 }
 
@@ -153,7 +155,7 @@ fn problem1(input: &Input) -> usize {
         .iter()
         .map(|x| {
             let sr = x.clone();
-            count_solutions(&mut UnboundCache::new(), &sr.springs, &sr.groups, None)
+            count_solutions(&mut Memo::new(), &sr.springs, &sr.groups, None)
         })
         .sum()
 }
@@ -163,7 +165,7 @@ fn problem2(input: &Input) -> usize {
         .iter()
         .map(|sr| {
             let sr = sr.multiply(5);
-            count_solutions(&mut UnboundCache::new(), &sr.springs, &sr.groups, None)
+            count_solutions(&mut Memo::new(), &sr.springs, &sr.groups, None)
         })
         .sum()
 }
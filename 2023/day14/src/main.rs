@@ -1,7 +1,9 @@
-use std::collections::HashMap;
 use std::fmt::Debug;
 
-use common::{answer, extensions::vecvec::VecVec, grid::Grid, nom::parse_grid, read_input};
+use common::{
+    answer, cycle::nth_state_by_key, extensions::vecvec::VecVec, grid::Grid, nom::parse_grid,
+    read_input,
+};
 use nom::{branch::alt, character::complete::char, combinator::map, IResult, Parser};
 
 fn main() {
@@ -27,7 +29,7 @@ fn parse(input: &str) -> Input {
     result.unwrap().1
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /** Represents the tiles on the platform. Ordering is important because "tilting" is based on sort order */
 enum Tile {
     Empty,
@@ -99,43 +101,21 @@ fn problem1(input: &Input) -> usize {
 }
 
 fn problem2(input: &Input) -> usize {
-    let mut platform: Platform = input.clone();
-    // keep a cache of the boards and the last cycle we saw that configuration
-    let mut platform_cache: HashMap<String, usize> = HashMap::new();
-
     let limit = 1_000_000_000;
 
-    // start on cycle 1
-    let mut current = 1;
-    let mut load = 0;
-
-    while current < limit {
-        // do the spin cycle and get the load
-        platform.spin_cycle();
-        load = platform.load();
-
-        // insert the current state of the board and get the last time we saw it
-        if let Some(previous) = platform_cache.insert(format!("{platform:?}"), current) {
-            // if we've seen it before, then we need to do some cool cycle math from 2022 day 17
-            let cycle_size = previous.abs_diff(current);
-            let skip_count = (limit - current) / cycle_size;
-            let skipped = skip_count * cycle_size;
-
-            println!(
-                "Found cycle at {current}. Skipping {skip_count} cycles of {cycle_size} size for {skipped}"
-            );
-
-            // skip that count
-            current += skipped;
-
-            // don't let it find a new cycle on the next line, just blow away everything
-            platform_cache.clear();
-        } else {
-            current += 1;
-        };
-    }
+    // the platform eventually settles into a loop, so skip ahead using the cycle math from 2022 day 17
+    let platform = nth_state_by_key(
+        input.clone(),
+        |platform| {
+            let mut platform = platform.clone();
+            platform.spin_cycle();
+            platform
+        },
+        |platform| platform.platform.points.clone(),
+        limit,
+    );
 
-    load
+    platform.load()
 }
 
 #[cfg(test)]
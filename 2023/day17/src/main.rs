@@ -1,13 +1,9 @@
-use std::{
-    cmp::Reverse,
-    collections::{BTreeMap, BinaryHeap},
-};
-
 use common::{
     answer,
-    grid::{CardinalDirection, Coord, Grid, GridSquare},
+    grid::{CardinalDirection, Coord, Grid},
     nom::{parse_grid, single_digit},
     read_input,
+    search::dijkstra,
 };
 use nom::{IResult, Parser};
 
@@ -27,27 +23,20 @@ fn parse(input: &str) -> Input {
     result.unwrap().1
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct State {
-    heat_loss: u32,
     current: Coord,
     consecutive_steps: u32,
     direction: CardinalDirection,
 }
 
 impl State {
-    fn new(square: GridSquare<u32>, direction: CardinalDirection, consecutive_steps: u32) -> Self {
-        Self {
-            current: square.coords,
-            heat_loss: *square.data,
-            direction,
-            consecutive_steps,
-        }
-    }
-
     fn get_eligible_directions(&self, min: u32, max: u32) -> Vec<CardinalDirection> {
         use CardinalDirection::*;
         match self.direction {
+            // we haven't moved yet, so start by going south or east
+            _ if self.consecutive_steps == 0 => vec![East, South],
+
             // if we're less than min, keep going
             x if self.consecutive_steps < min => vec![x],
 
@@ -63,85 +52,57 @@ impl State {
         }
     }
 
-    fn to_cache_key(&self) -> CacheKey {
-        // because we can't turn around, we can cut the cache key space in half by only considering vertical or horizontal directions
-        let dt = match self.direction {
-            CardinalDirection::North | CardinalDirection::South => DirectionType::Vertical,
-            CardinalDirection::East | CardinalDirection::West => DirectionType::Horizontal,
-        };
-        (self.current, dt, self.consecutive_steps)
-    }
-}
-
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-enum DirectionType {
-    Horizontal,
-    Vertical,
-}
-
-type CacheKey = ((usize, usize), DirectionType, u32);
-
-fn problem1(input: &Input) -> u32 {
-    dijkstra(input, 1, 3)
-}
-
-fn problem2(input: &Input) -> u32 {
-    dijkstra(input, 4, 10)
-}
-
-fn dijkstra(input: &Input, min: u32, max: u32) -> u32 {
-    let goal = (input.width - 1, input.height - 1);
+    fn successors(&self, input: &Input, min: u32, max: u32) -> Vec<(State, u32)> {
+        let neighbors = input.get(self.current).neighbors();
 
-    let mut seen: BTreeMap<CacheKey, u32> = BTreeMap::new();
-    let mut queue: BinaryHeap<Reverse<State>> = BinaryHeap::new();
+        self.get_eligible_directions(min, max)
+            .into_iter()
+            .filter_map(|dir| {
+                let neighbor = neighbors.get(dir).as_ref()?;
 
-    // start by going south and east
-    let initial_east = State::new(input.get((1, 0)), CardinalDirection::East, 1);
-    let initial_south = State::new(input.get((0, 1)), CardinalDirection::South, 1);
-
-    seen.insert(initial_east.to_cache_key(), 0);
-    queue.push(Reverse(initial_east));
-    seen.insert(initial_south.to_cache_key(), 0);
-    queue.push(Reverse(initial_south));
-
-    while let Some(Reverse(state)) = queue.pop() {
-        // if we're at the goal, mark this as best available
-        if state.current == goal {
-            return state.heat_loss;
-        }
-
-        let current = input.get(state.current);
-        let neighbors = current.neighbors();
-
-        // get all the eligible neighbors
-        for dir in state.get_eligible_directions(min, max) {
-            if let Some(neighbor) = neighbors.get(dir) {
-                let heat_loss = state.heat_loss + *neighbor.data;
-
-                let consecutive_steps = if dir == state.direction {
-                    state.consecutive_steps + 1
+                let consecutive_steps = if dir == self.direction {
+                    self.consecutive_steps + 1
                 } else {
                     1
                 };
 
                 let next = State {
                     current: neighbor.coords,
-                    heat_loss,
                     consecutive_steps,
                     direction: dir,
                 };
 
-                // figure out if we should go to the next state
-                let prev_cost = seen.get(&next.to_cache_key()).unwrap_or(&u32::MAX);
-                if next.heat_loss < *prev_cost {
-                    seen.insert(next.to_cache_key(), heat_loss);
-                    queue.push(Reverse(next));
-                }
-            }
-        }
+                Some((next, *neighbor.data))
+            })
+            .collect()
     }
+}
+
+fn problem1(input: &Input) -> u32 {
+    minimum_heat_loss(input, 1, 3)
+}
+
+fn problem2(input: &Input) -> u32 {
+    minimum_heat_loss(input, 4, 10)
+}
+
+fn minimum_heat_loss(input: &Input, min: u32, max: u32) -> u32 {
+    let goal = (input.width - 1, input.height - 1);
+
+    let start = State {
+        current: (0, 0),
+        consecutive_steps: 0,
+        direction: CardinalDirection::East,
+    };
+
+    let (heat_loss, _path) = dijkstra(
+        start,
+        |state| state.successors(input, min, max),
+        |state| state.current == goal,
+    )
+    .unwrap();
 
-    unreachable!()
+    heat_loss
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
-use std::{collections::BTreeMap, ops::Range};
+use std::collections::BTreeMap;
 
-use common::{answer, read_input};
+use common::{answer, geometry::Aabb, read_input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -111,35 +111,23 @@ impl<'a> Rule<'a> {
         }
     }
 
-    fn evaluate_ranged_part(&self, rp: &RangedPart) -> (RangedPart, RangedPart) {
-        let mut accepted_part = rp.clone();
-        let mut rejected_part = rp.clone();
-
-        // we only care about branches, not the fallthrough
-        if let Rule::Branch(xmas, comp, val, _target) = self {
-            let attribute = &rp.attributes[xmas];
-            let val = *val as usize;
-
-            let (accepted_range, rejected_range) = match comp {
-                Comparison::Greater => {
-                    let accepted = val + 1..attribute.end;
-                    let rejected = attribute.start..val + 1;
-
-                    (accepted, rejected)
-                }
-                Comparison::Less => {
-                    let accepted = attribute.start..val;
-                    let rejected = val..attribute.end;
-
-                    (accepted, rejected)
+    fn evaluate_ranged_part(&self, rp: &RangedPart) -> (Option<RangedPart>, Option<RangedPart>) {
+        match self {
+            // split the box of parts on the attribute, either side might end up empty
+            Rule::Branch(xmas, comp, val, _target) => {
+                let axis = *xmas as usize;
+                let val = *val as i64;
+
+                match comp {
+                    Comparison::Greater => {
+                        let (rejected, accepted) = rp.split(axis, val + 1);
+                        (accepted, rejected)
+                    }
+                    Comparison::Less => rp.split(axis, val),
                 }
-            };
-
-            accepted_part.attributes.insert(*xmas, accepted_range);
-            rejected_part.attributes.insert(*xmas, rejected_range);
+            }
+            Rule::Fallthrough(_) => (Some(*rp), None),
         }
-
-        (accepted_part, rejected_part)
     }
 
     fn target(&self) -> &str {
@@ -205,48 +193,31 @@ impl<'a> Workflows<'a> {
     /** Do a DFS on the workflows, going down each path and splitting the ranges based on the conditions */
     fn evaluate_ranged_part(&self, rule: &str, p: &RangedPart) -> usize {
         match rule {
-            "A" => p.count(),
+            "A" => p.volume() as usize,
             "R" => 0,
             _ => {
-                let rules = &self.map[rule];
-                let (total, _) = rules.iter().fold((0, p.clone()), |(total, rejected), r| {
-                    // split the ranges into two based on the rule
-                    let (accepted, rejected) = r.evaluate_ranged_part(&rejected);
-                    let total = total + self.evaluate_ranged_part(r.target(), &accepted);
+                let mut total = 0;
+                let mut rejected = Some(*p);
+                for r in &self.map[rule] {
+                    let Some(remaining) = rejected else {
+                        break;
+                    };
 
-                    (total, rejected)
-                });
+                    // split the ranges into two based on the rule
+                    let (accepted, rest) = r.evaluate_ranged_part(&remaining);
+                    if let Some(accepted) = accepted {
+                        total += self.evaluate_ranged_part(r.target(), &accepted);
+                    }
+                    rejected = rest;
+                }
                 total
             }
         }
     }
 }
 
-#[derive(Clone, Debug)]
-struct RangedPart {
-    attributes: BTreeMap<Xmas, Range<usize>>,
-}
-
-impl RangedPart {
-    fn new() -> Self {
-        let attributes = [
-            (Xmas::X, 1..4001),
-            (Xmas::M, 1..4001),
-            (Xmas::A, 1..4001),
-            (Xmas::S, 1..4001),
-        ];
-        Self {
-            attributes: attributes.into_iter().collect(),
-        }
-    }
-
-    fn count(&self) -> usize {
-        self.attributes
-            .values()
-            .map(|x| x.clone().count())
-            .product()
-    }
-}
+// each of the xmas attributes is one axis, in that order
+type RangedPart = Aabb<4>;
 
 fn problem1(input: &Input) -> u32 {
     let (workflows, parts) = input;
@@ -260,7 +231,7 @@ fn problem1(input: &Input) -> u32 {
 fn problem2(input: &Input) -> usize {
     let (workflows, _) = input;
 
-    workflows.evaluate_ranged_part("in", &RangedPart::new())
+    workflows.evaluate_ranged_part("in", &RangedPart::new([1; 4], [4000; 4]))
 }
 
 #[cfg(test)]
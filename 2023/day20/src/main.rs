@@ -5,7 +5,7 @@ use advent_2023_20::conjunction::Conjunction;
 use advent_2023_20::flip_flop::FlipFlop;
 use advent_2023_20::{ModuleKind, Pulse};
 
-use common::{answer, math::lcm, read_input};
+use common::{answer, math::lcm_all, read_input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -192,11 +192,7 @@ fn problem2(input: &Input) -> i64 {
                 // do we have everything we need?
                 if cycles_cache.len() == incoming_count {
                     // least common multiple the cycles
-                    return cycles_cache
-                        .values()
-                        .map(|x| *x as i64)
-                        .reduce(lcm)
-                        .unwrap();
+                    return lcm_all(cycles_cache.values().map(|x| *x as i64)).unwrap();
                 }
             }
 
@@ -1,8 +1,12 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    iter::successors,
+};
 
 use common::{
     answer,
     grid::{Coord, Grid, HasNeighbors},
+    math::sequence::sample_and_extrapolate,
     nom::parse_grid,
     read_input,
 };
@@ -90,96 +94,36 @@ fn infinite_neighbors((x, y): (isize, isize)) -> Vec<(isize, isize)> {
         .collect()
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-struct InfiniteState {
-    steps: u32,
-    position: (isize, isize),
-}
-
-impl PartialOrd for InfiniteState {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for InfiniteState {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other
-            .steps
-            .cmp(&self.steps)
-            .then(self.position.cmp(&other.position))
-    }
-}
-
-fn find_cycles(input: &Input, step_goal: usize) -> Vec<usize> {
+/// How many plots can be reached in exactly 0, 1, 2... steps, on the grid tiled out forever
+fn reachable_counts(input: &Input) -> impl Iterator<Item = i64> + '_ {
     let starting_position = input
         .iter()
         .find_map(|x| (x.data == &Tile::StartingPosition).then_some(x.coords))
         .map(|(x, y)| (x as isize, y as isize))
         .unwrap();
 
-    let mut queue: VecDeque<InfiniteState> = VecDeque::new();
-    queue.push_back(InfiniteState {
-        steps: 0,
-        position: starting_position,
-    });
-
-    let mut ending: BTreeSet<(isize, isize)> = BTreeSet::new();
-    let mut seen: BTreeSet<InfiniteState> = BTreeSet::new();
-
-    let mut last = 1usize;
-    let mut cycles = vec![];
-    while let Some(state) = queue.pop_front() {
-        ending.insert(state.position);
-
-        // when we move up into a higher step size, we need to check if we're at the right point for a quadratic fit
-        if last != state.steps as usize {
-            if last % input.width == step_goal % input.width {
-                cycles.push(ending.len());
-
-                // once we have three terms, we're good
-                if cycles.len() == 3 {
-                    return cycles;
-                }
-            }
-            ending.clear();
-
-            last = state.steps as usize;
-        }
-
-        for neighbor @ (nx, ny) in infinite_neighbors(state.position) {
-            let x = (nx.rem_euclid(input.width as isize)) as usize;
-            let y = (ny.rem_euclid(input.height as isize)) as usize;
-
-            if let Tile::Rock = input.get((x, y)).data {
-                continue;
-            }
-
-            let state = InfiniteState {
-                steps: state.steps + 1,
-                position: neighbor,
-            };
-
-            // only explore the space if we haven't been here before with the exact same steps left
-            if seen.insert(state) {
-                queue.push_back(state);
-            }
-        }
-    }
-
-    unreachable!()
+    let frontier = HashSet::from([starting_position]);
+    successors(Some(frontier), |frontier| {
+        let next = frontier
+            .iter()
+            .flat_map(|&position| infinite_neighbors(position))
+            .filter(|&(nx, ny)| {
+                let x = nx.rem_euclid(input.width as isize) as usize;
+                let y = ny.rem_euclid(input.height as isize) as usize;
+                input.get((x, y)).data != &Tile::Rock
+            })
+            .collect();
+        Some(next)
+    })
+    .map(|frontier| frontier.len() as i64)
 }
 
 fn problem2(input: &Input, step_goal: usize) -> usize {
-    let terms = find_cycles(input, step_goal);
-
-    let x = step_goal / input.width;
-
-    let b0 = terms[0];
-    let b1 = terms[1] - terms[0];
-    let b2 = terms[2] - terms[1];
-
-    b0 + b1 * x + (x * (x - 1) / 2) * (b2 - b1)
+    // the start has a clear run to the edges, so every time the steps cross another copy of the grid the area grows
+    // the same way, and the counts at those points are a quadratic
+    let counts = reachable_counts(input);
+    let (offset, period) = (step_goal % input.width, input.width);
+    sample_and_extrapolate(counts, offset, period, 2, step_goal) as usize
 }
 
 #[cfg(test)]
@@ -194,7 +138,7 @@ mod test {
     }
 
     #[test]
-    #[ignore = "overflow, probably need to fix this at some point"]
+    #[ignore = "the example has no clear run to the edges, so it isn't a quadratic this early"]
     fn second() {
         let input = include_str!("../test.txt");
         let input = parse(input);
@@ -203,4 +147,14 @@ mod test {
         assert_eq!(problem2(&input, 50), 1594);
         assert_eq!(problem2(&input, 100), 6536);
     }
+
+    #[test]
+    fn second_open_grid() {
+        // with no rocks at all, the plots that can be reached are the ones within n steps that are an even distance
+        // away from n, which is (n + 1)² of them
+        let input = parse(".....\n.....\n..S..\n.....\n.....");
+        for steps in [2, 7, 13, 504, 26501365] {
+            assert_eq!(problem2(&input, steps), (steps + 1).pow(2));
+        }
+    }
 }
@@ -3,10 +3,10 @@ use std::{
     fmt::{Debug, Display},
 };
 
-use common::{answer, read_input};
+use common::{answer, geometry::Aabb, read_input};
 use nom::{
     bytes::complete::tag,
-    character::complete::{newline, u32},
+    character::complete::{i64, newline},
     combinator::map,
     multi::separated_list1,
     sequence::{separated_pair, terminated},
@@ -28,8 +28,8 @@ fn parse(input: &str) -> Input {
     let mut idx = 0usize;
     let triple = |s| {
         map(
-            (terminated(u32, tag(",")), terminated(u32, tag(",")), u32),
-            |(x, y, z)| Triple::new(z, y, x),
+            (terminated(i64, tag(",")), terminated(i64, tag(",")), i64),
+            |(x, y, z)| [x, y, z],
         ).parse(s)
     };
     let result: IResult<&str, Input> = map(
@@ -46,67 +46,33 @@ fn parse(input: &str) -> Input {
     result.unwrap().1
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Triple {
-    z: u32,
-    y: u32,
-    x: u32,
-}
-
-impl Triple {
-    fn new(z: u32, y: u32, x: u32) -> Self {
-        Self { z, y, x }
-    }
-
-    fn below(&self) -> Self {
-        Self {
-            z: self.z - 1,
-            y: self.y,
-            x: self.x,
-        }
-    }
-}
+// the axes are x, y, then z going up
+const Z: usize = 2;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct Brick {
     id: usize,
-    min: Triple,
-    max: Triple,
-    cubes: BTreeSet<Triple>,
+    shape: Aabb<3>,
 }
 
 impl Brick {
-    fn new(id: usize, start: Triple, end: Triple) -> Self {
-        let min = start.min(end);
-        let max = start.max(end);
-        let cubes = Self::cubes(min, max);
+    fn new(id: usize, start: [i64; 3], end: [i64; 3]) -> Self {
         Self {
             id,
-            min,
-            max,
-            cubes,
+            shape: Aabb::from_corners(start, end),
         }
     }
 
-    fn on_ground(&self) -> bool {
-        self.min.z == 1
+    fn bottom(&self) -> i64 {
+        self.shape.min[Z]
     }
 
-    fn move_down(&mut self) {
-        self.min.z -= 1;
-        self.max.z -= 1;
-        // this is probably inefficient?
-        self.cubes = Self::cubes(self.min, self.max);
+    fn top(&self) -> i64 {
+        self.shape.max[Z]
     }
 
-    // generate all the cubes for this brick
-    fn cubes(min: Triple, max: Triple) -> BTreeSet<Triple> {
-        (min.z..=max.z)
-            .flat_map(|z| {
-                (min.y..=max.y)
-                    .flat_map(move |y| (min.x..=max.x).map(move |x| Triple::new(z, y, x)))
-            })
-            .collect()
+    fn drop_to(&mut self, z: i64) {
+        self.shape = self.shape.translate([0, 0, z - self.bottom()]);
     }
 }
 
@@ -115,7 +81,13 @@ impl Display for Brick {
         write!(
             f,
             "{}: {},{},{}~{},{},{}",
-            self.id, self.min.z, self.min.y, self.min.x, self.max.z, self.max.y, self.max.x
+            self.id,
+            self.shape.min[0],
+            self.shape.min[1],
+            self.shape.min[2],
+            self.shape.max[0],
+            self.shape.max[1],
+            self.shape.max[2]
         )
     }
 }
@@ -128,7 +100,12 @@ impl PartialOrd for Brick {
 
 impl Ord for Brick {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.min.cmp(&other.min).then(self.max.cmp(&other.max))
+        // lowest first, so everything underneath a brick has landed before it falls
+        (self.bottom(), self.shape.min, self.shape.max).cmp(&(
+            other.bottom(),
+            other.shape.min,
+            other.shape.max,
+        ))
     }
 }
 
@@ -137,48 +114,34 @@ fn settle(input: &[Brick]) -> Input {
     let mut bricks = input.to_vec();
     bricks.sort();
 
-    // The 3D tower, with brick id being the value
-    let mut settled: BTreeMap<Triple, usize> = BTreeMap::new();
+    // The 3D tower, in the order the bricks landed
+    let mut settled: Vec<Brick> = vec![];
 
     // the "graph" indicating which bricks support other bricks
     let mut holding_up: SupportMap = BTreeMap::new();
     let mut sitting_on: SupportMap = BTreeMap::new();
 
-    for brick in bricks.iter_mut() {
+    for mut brick in bricks {
         // set up our graph from here
         holding_up.insert(brick.id, BTreeSet::new());
         sitting_on.insert(brick.id, BTreeSet::new());
 
-        // move down until we find something below us
-        let bricks_below = loop {
-            // if we hit the ground, return nothing
-            if brick.on_ground() {
-                break vec![];
-            }
-
-            let bricks_below: Vec<usize> = brick
-                .cubes
-                .iter()
-                .filter_map(|c| settled.get(&c.below()))
-                .cloned()
-                .collect();
-
-            // if there are bricks below us, return their IDs
-            if !bricks_below.is_empty() {
-                break bricks_below;
-            }
-
-            brick.move_down();
-        };
-
-        // now that we dropped all the way, set the id where we landed
-        settled.extend(brick.cubes.iter().map(|c| (*c, brick.id)));
-
-        // set up the graph for each of the bricks
-        for below in bricks_below {
-            holding_up.entry(below).or_default().insert(brick.id);
-            sitting_on.entry(brick.id).or_default().insert(below);
+        // anything directly underneath is in the way, and the highest of those is where we stop. if there's
+        // nothing, we hit the ground
+        let underneath: Vec<&Brick> = settled
+            .iter()
+            .filter(|s| s.shape.projections_overlap(&brick.shape, Z))
+            .collect();
+        let landing = underneath.iter().map(|s| s.top() + 1).max().unwrap_or(1);
+        brick.drop_to(landing);
+
+        // set up the graph for each of the bricks we're resting on
+        for below in underneath.iter().filter(|s| s.top() + 1 == landing) {
+            holding_up.entry(below.id).or_default().insert(brick.id);
+            sitting_on.entry(brick.id).or_default().insert(below.id);
         }
+
+        settled.push(brick);
     }
 
     (holding_up, sitting_on)
@@ -1,8 +1,6 @@
-use std::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
-
 use common::{
     answer,
-    grid::{CardinalDirection, Coord, Grid},
+    grid::{contract, contract_directed, CardinalDirection, Grid, JunctionGraph},
     nom::parse_grid,
     read_input,
 };
@@ -41,34 +39,21 @@ enum Tile {
     SlopeWest,
 }
 
-const DIRECTIONS: [CardinalDirection; 4] = [
-    CardinalDirection::North,
-    CardinalDirection::West,
-    CardinalDirection::East,
-    CardinalDirection::South,
-];
-
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct State {
-    steps: usize,
-    position: Coord,
-    visited: BTreeSet<Coord>,
-}
-
-impl State {
-    fn move_to(&self, position: Coord, cost: usize) -> Option<Self> {
-        let mut visited = self.visited.clone();
-        visited.insert(position).then_some(Self {
-            steps: self.steps + cost,
-            position,
-            visited,
-        })
+impl Tile {
+    fn slope(&self) -> Option<CardinalDirection> {
+        match self {
+            Tile::SlopeNorth => Some(CardinalDirection::North),
+            Tile::SlopeSouth => Some(CardinalDirection::South),
+            Tile::SlopeEast => Some(CardinalDirection::East),
+            Tile::SlopeWest => Some(CardinalDirection::West),
+            _ => None,
+        }
     }
 }
 
-fn get_longest_path<F>(input: &Input, valid: F) -> usize
+fn get_longest_path<F>(input: &Input, compress: F) -> usize
 where
-    F: Copy + Fn(Tile, CardinalDirection, Tile) -> bool,
+    F: Fn(&Input, &[(usize, usize)]) -> JunctionGraph,
 {
     let start = (
         input
@@ -92,128 +77,26 @@ where
         input.height - 1,
     );
 
-    let intersections = get_intersections(input, start, end);
-
     // do some edge compression because the grid has a ton of hallways
-    let edges: BTreeMap<Coord, Vec<(Coord, usize)>> = intersections
-        .iter()
-        .map(|&i| (i, get_edges(input, i, &intersections, valid)))
-        .collect();
-
-    let start = State {
-        steps: 0,
-        position: start,
-        visited: BTreeSet::new(),
-    };
-
-    let mut queue: BinaryHeap<State> = BinaryHeap::new();
-    queue.push(start);
-
-    let mut longest = 0;
-    while let Some(state) = queue.pop() {
-        if state.position == end {
-            longest = longest.max(state.steps);
-            continue;
-        }
+    let graph = compress(input, &[start, end]);
 
-        for &(next, cost) in edges.get(&state.position).unwrap() {
-            if let Some(new_state) = state.move_to(next, cost) {
-                queue.push(new_state);
-            }
-        }
-    }
-
-    longest
-}
-
-fn get_edges(
-    input: &Input,
-    start: Coord,
-    intersections: &BTreeSet<(usize, usize)>,
-    valid: impl Fn(Tile, CardinalDirection, Tile) -> bool,
-) -> Vec<(Coord, usize)> {
-    let mut results: Vec<(Coord, usize)> = vec![];
-    let mut visited: BTreeSet<Coord> = BTreeSet::new();
-    let mut queue = VecDeque::new();
-    queue.push_back((start, 0));
-
-    while let Some((current, cost)) = queue.pop_front() {
-        if intersections.contains(&current) && current != start {
-            results.push((current, cost));
-            continue;
-        }
-
-        let current = input.get(current);
-        let neighbors = current.neighbors();
-
-        let valid_neighbors = DIRECTIONS
-            .iter()
-            .filter_map(|d| neighbors.get(*d).map(|n| (d, n)));
-
-        for (d, n) in valid_neighbors {
-            if valid(*current.data, *d, *n.data) && visited.insert(n.coords) {
-                queue.push_back((n.coords, cost + 1));
-            }
-        }
-    }
-
-    results
-}
-
-fn get_intersections(input: &Input, start: Coord, end: Coord) -> BTreeSet<(usize, usize)> {
-    let mut intersections: BTreeSet<Coord> = input
-        .iter()
-        .filter(|x| x.data != &Tile::Forest)
-        .filter_map(|x| {
-            // we only care about non-forest tiles
-            let valid_neighbors = x
-                .neighbors()
-                .iter()
-                .filter(|x| x.data != &Tile::Forest)
-                .count();
-
-            (valid_neighbors > 2).then_some(x.coords)
-        })
-        .collect();
-
-    // we also care about the start and end
-    intersections.insert(start);
-    intersections.insert(end);
-
-    intersections
+    graph
+        .longest_path(graph.node(start).unwrap(), graph.node(end).unwrap())
+        .unwrap()
 }
 
 fn problem1(input: &Input) -> usize {
-    let valid = |current, dir, next| match (current, dir, next) {
-        // can't go into the forest
-        (_, _, Tile::Forest) => false,
-
-        // can only go down the corresponding slope
-        (Tile::SlopeNorth, d, _) => d == CardinalDirection::North,
-        (Tile::SlopeSouth, d, _) => d == CardinalDirection::South,
-        (Tile::SlopeEast, d, _) => d == CardinalDirection::East,
-
-        // can't go up the opposing slope
-        (_, CardinalDirection::North, Tile::SlopeSouth) => false,
-        (_, CardinalDirection::South, Tile::SlopeNorth) => false,
-        (_, CardinalDirection::East, Tile::SlopeWest) => false,
-        (_, CardinalDirection::West, Tile::SlopeEast) => false,
-
-        _ => true,
-    };
-
-    get_longest_path(input, valid)
+    // can only go down slopes, not up them
+    get_longest_path(input, |input, points| {
+        contract_directed(input, |t| t != &Tile::Forest, Tile::slope, points)
+    })
 }
 
 fn problem2(input: &Input) -> usize {
-    let valid = |current, dir, next| match (current, dir, next) {
-        // can't go into the forest
-        (_, _, Tile::Forest) => false,
-
-        _ => true,
-    };
-
-    get_longest_path(input, valid)
+    // slopes are just paths now
+    get_longest_path(input, |input, points| {
+        contract(input, |t| t != &Tile::Forest, points)
+    })
 }
 
 #[cfg(test)]
@@ -1,6 +1,10 @@
 use std::ops::RangeInclusive;
 
-use common::{answer, read_input};
+use common::{
+    answer,
+    linalg::{Matrix, Rational},
+    read_input,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::{i128, newline, space0},
@@ -9,7 +13,6 @@ use nom::{
     sequence::{preceded, separated_pair},
     IResult, Parser,
 };
-use z3::{ast::Int, SatResult};
 
 fn main() {
     let input = read_input!();
@@ -115,27 +118,64 @@ fn problem1(input: &Input) -> usize {
     count_future_xy_intersections(input, 200000000000000..=400000000000000)
 }
 
+type Vector = (i128, i128, i128);
+
+fn sub(a: Vector, b: Vector) -> Vector {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    )
+}
+
+/* The rock at P moving at V hits every hailstone, so (P - p) × (V - v) = 0 for each of them. Expanding that
+ * leaves a P × V term that's the same for every hailstone, so subtracting the equations for two hailstones i and j
+ * gets rid of it and leaves something linear:
+ *
+ *     P × (vj - vi) + (pj - pi) × V = pj × vj - pi × vi
+ *
+ * Two pairs of hailstones give six equations for the six unknowns.
+ */
 fn problem2(input: &Input) -> i64 {
-    let s = z3::Solver::new();
-    let [fx, fy, fz, fdx, fdy, fdz] = ["fx", "fy", "fz", "fdx", "fdy", "fdz"].map(Int::new_const);
-
-    for (i, hailstone) in input.iter().enumerate().take(3) {
-        let (x, y, z) = hailstone.position;
-        let (dx, dy, dz) = hailstone.velocity;
-        let [x, y, z, dx, dy, dz] = [x, y, z, dx, dy, dz].map(|v| Int::from_i64(v as _));
-        let t = Int::new_const(format!("t{i}"));
-
-        s.assert(t.ge(0));
-        s.assert((x + dx * &t).eq(&fx + &fdx * &t));
-        s.assert((y + dy * &t).eq(&fy + &fdy * &t));
-        s.assert((z + dz * &t).eq(&fz + &fdz * &t));
-    }
+    // measure everything from the first hailstone to keep the numbers small
+    let origin = input[0].position;
+    let hailstones = input
+        .iter()
+        .map(|h| (sub(h.position, origin), h.velocity))
+        .collect::<Vec<_>>();
+
+    hailstones
+        .windows(3)
+        .find_map(|triple| {
+            let (p0, v0) = triple[0];
+            let mut rows = vec![];
+            let mut rhs = vec![];
+            for &(p, v) in &triple[1..] {
+                let (wx, wy, wz) = sub(v, v0);
+                let (dx, dy, dz) = sub(p, p0);
+                let (rx, ry, rz) = sub(cross(p, v), cross(p0, v0));
+
+                rows.push(vec![0, wz, -wy, 0, -dz, dy]);
+                rows.push(vec![-wz, 0, wx, dz, 0, -dx]);
+                rows.push(vec![wy, -wx, 0, -dy, dx, 0]);
+                rhs.extend([rx, ry, rz].map(Rational::from));
+            }
 
-    assert_eq!(s.check(), SatResult::Sat);
-    s.get_model()
-        .and_then(|m| m.eval(&(&fx + &fy + &fz), true))
-        .and_then(|x| x.as_i64())
-        .unwrap()
+            // some hailstones are parallel, which doesn't pin the rock down
+            let a = Matrix::from_integers(&rows);
+            if a.rank() < 6 {
+                return None;
+            }
+
+            let x = a.solve(&rhs)?;
+            let total = &x[0] + &x[1] + &x[2];
+            Some(total.to_integer()? + origin.0 + origin.1 + origin.2)
+        })
+        .expect("the rock should hit every hailstone") as i64
 }
 
 #[cfg(test)]
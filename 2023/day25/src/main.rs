@@ -1,6 +1,6 @@
 use std::collections::BTreeSet;
 
-use common::{answer, read_input};
+use common::{answer, graph::FlowGraph, read_input};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, newline},
@@ -8,7 +8,6 @@ use nom::{
     sequence::separated_pair,
     IResult, Parser,
 };
-use rustworkx_core::{connectivity::stoer_wagner_min_cut, petgraph::graphmap::UnGraphMap, Result};
 
 fn main() {
     let input = read_input!();
@@ -37,17 +36,16 @@ fn problem1(input: &Input) -> usize {
         .flat_map(|(from, tos)| tos.iter().map(|to| (*from, *to)))
         .collect();
 
-    let g = UnGraphMap::<&str, ()>::from_edges(&all_wires);
+    let (g, components) = FlowGraph::from_labelled_edges(all_wires);
 
-    let min_cut_res: Result<Option<(usize, Vec<_>)>> = stoer_wagner_min_cut(&g, |_| Ok(1));
-    let (wires_to_cut, p1) = min_cut_res.unwrap().unwrap();
+    let (wires_to_cut, p1) = g.min_cut().unwrap();
 
     if wires_to_cut != 3 {
         panic!("Something went wrong")
     }
 
     let size1 = p1.len();
-    let size2 = g.node_count() - p1.len();
+    let size2 = components.len() - p1.len();
 
     size1 * size2
 }
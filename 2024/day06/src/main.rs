@@ -1,7 +1,7 @@
 use common::{
     answer,
     grid::{CardinalDirection, Grid, Position},
-    nom::parse_grid,
+    nom::{parse_grid_with_markers, Marker, Markers},
     read_input,
 };
 use nom::{branch::alt, character::complete::char, combinator::map, IResult, Parser};
@@ -15,23 +15,28 @@ fn main() {
     answer!(problem2(&input));
 }
 
-type Input = Grid<Tile>;
+type Input = (Grid<Tile>, Position);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Tile {
-    Guard,
     Obstruction,
     Space,
 }
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> = parse_grid(alt((
-        map(char('.'), |_| Tile::Space),
-        map(char('^'), |_| Tile::Guard),
-        map(char('#'), |_| Tile::Obstruction),
-    ))).parse(input);
-
-    result.unwrap().1
+    let result: IResult<&str, (Grid<Tile>, Markers)> = parse_grid_with_markers(
+        [Marker::unique('^', Tile::Space)],
+        alt((
+            map(char('.'), |_| Tile::Space),
+            map(char('#'), |_| Tile::Obstruction),
+        )),
+    )
+    .parse(input);
+
+    // the guard starts off facing up
+    let (grid, markers) = result.unwrap().1;
+    let start = Position::new(markers.get('^'), CardinalDirection::North);
+    (grid, start)
 }
 
 enum WalkResult {
@@ -39,8 +44,8 @@ enum WalkResult {
     Loop,
 }
 
-fn walk(grid: &Grid<Tile>) -> WalkResult {
-    let mut position = get_start(grid);
+fn walk(grid: &Grid<Tile>, start: Position) -> WalkResult {
+    let mut position = start;
     let mut visited: Vec<_> = vec![];
     let mut turns: BTreeSet<_> = BTreeSet::new();
     visited.push(position);
@@ -61,17 +66,8 @@ fn walk(grid: &Grid<Tile>) -> WalkResult {
     WalkResult::Leave(visited)
 }
 
-fn get_start(input: &Input) -> Position {
-    input
-        .iter()
-        .find_map(|x| {
-            (x.data == &Tile::Guard).then_some(Position::new(x.coords, CardinalDirection::North))
-        })
-        .unwrap()
-}
-
-fn problem1(input: &Input) -> usize {
-    let WalkResult::Leave(visited) = walk(input) else {
+fn problem1((grid, start): &Input) -> usize {
+    let WalkResult::Leave(visited) = walk(grid, *start) else {
         panic!("Something's wrong with the grid")
     };
 
@@ -79,19 +75,22 @@ fn problem1(input: &Input) -> usize {
     unique.len()
 }
 
-fn problem2(input: &Input) -> usize {
-    let WalkResult::Leave(visited) = walk(input) else {
+fn problem2((grid, start): &Input) -> usize {
+    let WalkResult::Leave(visited) = walk(grid, *start) else {
         panic!("Something's wrong with the grid")
     };
 
     let mut obstacles: BTreeSet<_> = BTreeSet::new();
     for pos in &visited {
-        let mut grid = input.clone();
-        if grid.get(pos.0).data == &Tile::Space {
-            grid.set(pos.0, Tile::Obstruction);
+        // the guard would notice an obstruction being put where they're standing
+        if pos.0 == start.0 {
+            continue;
         }
 
-        if let WalkResult::Loop = walk(&grid) {
+        let mut grid = grid.clone();
+        grid.set(pos.0, Tile::Obstruction);
+
+        if let WalkResult::Loop = walk(&grid, *start) {
             obstacles.insert(pos.0);
         }
     }
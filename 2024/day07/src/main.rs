@@ -1,4 +1,4 @@
-use common::{answer, read_input};
+use common::{answer, digits::concat, read_input};
 use nom::{
     bytes::complete::tag,
     character::complete::{newline, u64},
@@ -27,13 +27,6 @@ fn parse(input: &str) -> Input {
     result.unwrap().1
 }
 
-/// concat two numbers (e.g. concat_numbers(123, 45) == 12345)
-fn concat_numbers(a: u64, b: u64) -> u64 {
-    let exp = (b as f64).log10().ceil() as u32;
-    // multiple a by 10^c to "shift" it over so we can concat with b by adding
-    a * 10_u64.pow(exp) + b
-}
-
 fn is_valid(test_value: &u64, numbers: &[u64], allow_concat: bool) -> bool {
     let mut v = VecDeque::new();
     v.push_back((0, numbers[0]));
@@ -49,7 +42,7 @@ fn is_valid(test_value: &u64, numbers: &[u64], allow_concat: bool) -> bool {
             v.push_back((new_idx, current + new_num));
             v.push_back((new_idx, current * new_num));
             if allow_concat {
-                v.push_back((new_idx, concat_numbers(current, *new_num)));
+                v.push_back((new_idx, concat(current, *new_num).unwrap()));
             }
         } else if current == *test_value {
             // if we're done with the numbers and we hit the test value, return true
@@ -1,4 +1,8 @@
-use common::{answer, read_input};
+use common::{
+    answer,
+    digits::{digit_count, split_at_digit},
+    read_input,
+};
 use itertools::Itertools;
 use nom::{bytes::complete::tag, character::complete::u64, multi::separated_list1, IResult, Parser};
 use std::collections::HashMap;
@@ -24,11 +28,9 @@ fn blink_single(stone: u64) -> Vec<u64> {
         return vec![1];
     }
 
-    let digits = (stone as f64).log10().floor() as u64 + 1;
+    let digits = digit_count(stone);
     if digits.is_multiple_of(2) {
-        let half = 10_f64.powi((digits / 2) as i32);
-        let left = stone / half as u64;
-        let right = stone % half as u64;
+        let (left, right) = split_at_digit(stone, digits / 2);
         return vec![left, right];
     }
 
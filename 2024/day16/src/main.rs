@@ -3,9 +3,10 @@ use common::{
     grid::{CardinalDirection, Coord, Grid},
     nom::parse_grid,
     read_input,
+    search::{dijkstra_all, ShortestPathDag},
 };
 use nom::{branch::alt, character::complete::char, combinator::map, IResult, Parser};
-use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::collections::BTreeSet;
 
 fn main() {
     let input = read_input!();
@@ -36,127 +37,73 @@ fn parse(input: &str) -> Input {
     result.unwrap().1
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct State {
-    cost: usize,
     position: Coord,
     facing: CardinalDirection,
-    tiles: Vec<Coord>,
 }
 impl State {
     fn new(position: Coord) -> Self {
         Self {
-            cost: 0,
             position,
             facing: CardinalDirection::East,
-            tiles: vec![position],
         }
     }
-    fn moves(&self, grid: &Grid<Tile>) -> Vec<State> {
+    fn moves(&self, grid: &Grid<Tile>) -> Vec<(State, usize)> {
         let mut valid = vec![];
-        valid.push(Self {
-            facing: self.facing.turn_left(),
-            cost: self.cost + 1000,
-            tiles: self.tiles.clone(),
-            ..*self
-        });
-
-        valid.push(Self {
-            facing: self.facing.turn_right(),
-            cost: self.cost + 1000,
-            tiles: self.tiles.clone(),
-            ..*self
-        });
+        valid.push((
+            Self {
+                facing: self.facing.turn_left(),
+                ..*self
+            },
+            1000,
+        ));
+
+        valid.push((
+            Self {
+                facing: self.facing.turn_right(),
+                ..*self
+            },
+            1000,
+        ));
 
         if let Some(forward) = grid
             .get_neighbor(self.position, self.facing)
             .filter(|x| matches!(x.data, Tile::Space | Tile::End | Tile::Start))
-            .filter(|x| !self.tiles.contains(&x.coords))
         {
-            let mut new_tiles = self.tiles.clone();
-            new_tiles.push(forward.coords);
-            valid.push(Self {
-                position: forward.coords,
-                cost: self.cost + 1,
-                tiles: new_tiles,
-                ..*self
-            });
+            valid.push((
+                Self {
+                    position: forward.coords,
+                    ..*self
+                },
+                1,
+            ));
         }
 
         valid
     }
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-fn solve(input: &Input) -> State {
+fn solve(input: &Input) -> ShortestPathDag<State, usize> {
     let position = input.find(Tile::Start).map(|x| x.coords).unwrap();
 
-    let mut seen: BTreeMap<(Coord, CardinalDirection), usize> = BTreeMap::new();
-    let mut queue = BinaryHeap::from_iter([State::new(position)]);
-    let mut best: Option<State> = None;
-    while let Some(current) = queue.pop() {
-        let square = input.get(current.position);
-
-        // don't continue if we're already over a previous best
-        if let Some(previous_best) = &best {
-            if current.cost > previous_best.cost {
-                break;
-            }
-        }
-
-        // have we already been here?
-        let previous = seen
-            .entry((current.position, current.facing))
-            .or_insert(usize::MAX);
-
-        if *previous < current.cost {
-            continue;
-        } else {
-            *previous = current.cost;
-        }
-
-        // did we hit an end?
-        if square.data == &Tile::End {
-            if let Some(previous_best) = &mut best {
-                if previous_best.cost == current.cost {
-                    previous_best.tiles.extend(&current.tiles);
-                    continue;
-                } else {
-                    continue;
-                }
-            } else {
-                best = Some(current.clone());
-            }
-        }
-
-        // we're not there yet, keep looking
-        for valid_move in &current.moves(input) {
-            queue.push(valid_move.clone());
-        }
-    }
-
-    best.unwrap()
+    dijkstra_all(
+        State::new(position),
+        |state| state.moves(input),
+        |state| input.get(state.position).data == &Tile::End,
+    )
+    .unwrap()
 }
 
 fn problem1(input: &Input) -> usize {
-    solve(input).cost
+    solve(input).cost()
 }
 
 fn problem2(input: &Input) -> usize {
     solve(input)
-        .tiles
+        .nodes()
         .into_iter()
+        .map(|state| state.position)
         .collect::<BTreeSet<_>>()
         .len()
 }
@@ -1,5 +1,6 @@
 use common::{
     answer,
+    digits::from_digits,
     extensions::PointExt,
     grid::{CardinalDirection, Coord, Grid},
     nom::{parse_grid, single_digit},
@@ -93,18 +94,11 @@ enum NumericKeypad {
 
 impl NumericKeypad {
     fn to_number(seq: &[NumericKeypad]) -> usize {
-        let mut numeric_part = 0_usize;
-        for x in seq {
-            match x {
-                NumericKeypad::Accept => {}
-                // skip leading zeros
-                NumericKeypad::Number(0) if numeric_part == 0 => {}
-                NumericKeypad::Number(x) => {
-                    numeric_part = (numeric_part * 10) + *x as usize;
-                }
-            }
-        }
-        numeric_part
+        let digits = seq.iter().filter_map(|x| match x {
+            NumericKeypad::Number(x) => Some(*x),
+            NumericKeypad::Accept => None,
+        });
+        from_digits(digits).unwrap()
     }
 }
 
@@ -1,5 +1,11 @@
-use common::{answer, read_input};
-use itertools::Itertools;
+use common::{
+    answer,
+    graph::{
+        clique::{k_cliques, maximum_clique},
+        Interner,
+    },
+    read_input,
+};
 use nom::{
     bytes::complete::take,
     character::complete::{char, newline},
@@ -7,7 +13,6 @@ use nom::{
     sequence::separated_pair,
     IResult, Parser,
 };
-use std::{cmp::Reverse, collections::{HashMap, HashSet}};
 
 fn main() {
     let input = read_input!();
@@ -28,88 +33,26 @@ fn parse(input: &str) -> Input<'_> {
     result.unwrap().1
 }
 
-fn get_computers<'a>(input: &'a Input) -> HashMap<&'a str, Vec<&'a str>> {
-    input.iter().fold(HashMap::new(), |mut acc, (a, b)| {
-        acc.entry(*a)
-            .and_modify(|x: &mut Vec<&str>| x.push(b))
-            .or_insert(vec![b]);
-        acc.entry(*b)
-            .and_modify(|x: &mut Vec<&str>| x.push(a))
-            .or_insert(vec![a]);
-        acc
-    })
+fn get_computers<'a>(input: &Input<'a>) -> (Interner<&'a str>, Vec<Vec<usize>>) {
+    Interner::undirected(input.iter().copied())
 }
 
 fn problem1(input: &Input) -> usize {
-    let computers = get_computers(input);
-    let mut triples: HashSet<Vec<&str>> = HashSet::new();
-    for (a, x) in computers.iter().filter(|(k, _)| k.starts_with("t")) {
-        for &b in x {
-            for c in computers.get(&b).unwrap() {
-                if computers.get(c).unwrap().contains(a) {
-                    let mut triple = vec![a, b, c];
-                    triple.sort();
-                    triples.insert(triple);
-                }
-            }
-        }
-    }
-
-    triples.len()
-}
-
-// https://en.wikipedia.org/wiki/Bron–Kerbosch_algorithm
-fn find_cliques<'a>(
-    computers: &HashMap<&'a str, Vec<&'a str>>,
-    potential: Vec<&'a str>,
-    mut remaining: Vec<&'a str>,
-    mut skip: Vec<&'a str>,
-) -> Vec<Vec<&'a str>> {
-    if remaining.is_empty() && skip.is_empty() {
-        return vec![potential];
-    }
-
-    let mut cliques = vec![];
-
-    while let Some(node) = remaining.pop() {
-        let mut new_potential = potential.clone();
-        new_potential.push(node);
+    let (labels, computers) = get_computers(input);
 
-        let Some(neighbors) = computers.get(node) else {
-            continue;
-        };
-
-        let new_remaining = remaining
-            .iter()
-            .filter(|x| neighbors.contains(x))
-            .cloned()
-            .collect_vec();
-        let new_skip = skip
-            .iter()
-            .filter(|x| neighbors.contains(x))
-            .cloned()
-            .collect_vec();
-
-        let result = find_cliques(computers, new_potential, new_remaining, new_skip);
-        cliques.extend_from_slice(&result);
-
-        skip.push(node);
-    }
-
-    cliques
+    k_cliques(&computers, 3)
+        .iter()
+        .filter(|triple| triple.iter().any(|&c| labels.label(c).starts_with("t")))
+        .count()
 }
 
 fn problem2(input: &Input) -> String {
-    let computers = get_computers(input);
+    let (labels, computers) = get_computers(input);
 
-    let nodes = computers.keys().cloned().collect_vec();
-    let result = find_cliques(&computers, vec![], nodes.clone(), vec![]);
-    let mut max_clique = result
-        .iter()
-        .sorted_by_key(|x| Reverse(x.len()))
-        .next()
-        .cloned()
-        .unwrap();
+    let mut max_clique: Vec<&str> = maximum_clique(&computers)
+        .into_iter()
+        .map(|c| *labels.label(c))
+        .collect();
 
     max_clique.sort();
     max_clique.join(",")
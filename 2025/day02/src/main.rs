@@ -1,4 +1,8 @@
-use common::{answer, digits, read_input};
+use common::{
+    answer,
+    digits::{repeating_pattern, repeats},
+    read_input,
+};
 use nom::{
     bytes::complete::tag, character::complete::u64, multi::separated_list1,
     sequence::separated_pair, IResult, Parser,
@@ -22,34 +26,16 @@ fn parse(input: &str) -> Input {
 }
 
 fn problem1(x: &Input) -> u64 {
-    fn invalid(x: u64) -> Option<u64> {
-        let d = digits(x as usize);
-        let (fst, snd) = d.split_at(d.len() / 2);
-        fst.iter().eq(snd.iter()).then_some(x)
-    }
-
     x.iter()
         .flat_map(|(a, b)| *a..=*b)
-        .filter_map(invalid)
+        .filter(|&x| repeats(x, 2))
         .sum()
 }
 
 fn problem2(x: &Input) -> u64 {
-    fn invalid(x: u64) -> Option<u64> {
-        let d = digits(x as usize);
-
-        let invalid = (1..=(d.len() / 2)).rev().find(|n| {
-            let mut chunks = d.chunks(*n);
-            let first = chunks.next().unwrap();
-            chunks.all(|x| first.eq(x))
-        });
-
-        invalid.map(|_| x)
-    }
-
     x.iter()
         .flat_map(|(a, b)| *a..=*b)
-        .filter_map(invalid)
+        .filter(|&x| repeating_pattern(x).is_some())
         .sum()
 }
 
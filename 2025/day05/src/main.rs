@@ -1,5 +1,4 @@
-use common::{answer, extensions::RangeExt, read_input};
-use std::ops::RangeInclusive;
+use common::{answer, interval::IntervalSet, read_input};
 
 use nom::{
     bytes::complete::tag,
@@ -23,7 +22,7 @@ fn main() {
 
 type Input = Inventory;
 struct Inventory {
-    ranges: Vec<RangeInclusive<u64>>,
+    ranges: IntervalSet<u64>,
     items: Vec<u64>,
 }
 
@@ -37,7 +36,10 @@ fn parse(input: &str) -> Input {
             tag("\n\n"),
             separated_list1(newline, u64),
         ),
-        |(ranges, items)| Inventory { ranges, items },
+        |(ranges, items)| Inventory {
+            ranges: ranges.into_iter().collect(),
+            items,
+        },
     )
     .parse(input);
 
@@ -45,43 +47,11 @@ fn parse(input: &str) -> Input {
 }
 
 fn problem1(x: &Input) -> usize {
-    x.items
-        .iter()
-        .filter(|i| x.ranges.iter().any(|r| r.contains(i)))
-        .count()
-}
-
-fn reduce_ranges(x: &[RangeInclusive<u64>]) -> Option<Vec<RangeInclusive<u64>>> {
-    let (updated, result) = x.iter().fold((false, vec![]), |(updated, mut acc), r| {
-        if let Some(overlap) = acc
-            .iter()
-            .position(|existing: &RangeInclusive<u64>| existing.partially_contains(r))
-        {
-            let overlap = acc.get_mut(overlap).unwrap();
-
-            let start = overlap.start().min(r.start());
-            let end = overlap.end().max(r.end());
-            let merged = *start..=*end;
-
-            *overlap = merged;
-            (true, acc)
-        } else {
-            acc.push(r.clone());
-            (updated, acc)
-        }
-    });
-
-    updated.then_some(result)
+    x.items.iter().filter(|&&i| x.ranges.contains(i)).count()
 }
 
 fn problem2(x: &Input) -> usize {
-    let mut r = x.ranges.clone();
-
-    while let Some(new_r) = reduce_ranges(&r) {
-        r = new_r;
-    }
-
-    r.into_iter().map(|x| x.count()).sum()
+    x.ranges.len() as usize
 }
 
 #[cfg(test)]
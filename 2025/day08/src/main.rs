@@ -1,6 +1,7 @@
-use std::collections::{BTreeMap, BinaryHeap};
-
-use common::{answer, read_input};
+use common::{
+    answer, read_input,
+    union_find::{kruskal, UnionFind},
+};
 use itertools::Itertools;
 use nom::{
     bytes::tag,
@@ -19,99 +20,57 @@ fn main() {
 }
 
 type JunctionBox = (i64, i64, i64);
-type Input = BTreeMap<JunctionBox, usize>;
+type Input = Vec<JunctionBox>;
 
 fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> = map(
-        separated_list1(
-            newline,
-            map(separated_list1(tag(","), i64), |x| (x[0], x[1], x[2])),
-        ),
-        |x| BTreeMap::from_iter(x.iter().enumerate().map(|(id, x)| (*x, id))),
+    let result: IResult<&str, Input> = separated_list1(
+        newline,
+        map(separated_list1(tag(","), i64), |x| (x[0], x[1], x[2])),
     )
     .parse(input);
 
     result.unwrap().1
 }
 
-#[derive(Eq, PartialEq)]
-struct Pair(JunctionBox, JunctionBox);
-impl Pair {
-    fn distance(&self) -> i64 {
-        let (ax, ay, az) = self.0;
-        let (bx, by, bz) = self.1;
-        ((ax - bx).pow(2) + (ay - by).pow(2) + (az - bz).pow(2)).isqrt()
-    }
-}
-
-impl PartialOrd for Pair {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+fn distance((ax, ay, az): &JunctionBox, (bx, by, bz): &JunctionBox) -> i64 {
+    (ax - bx).pow(2) + (ay - by).pow(2) + (az - bz).pow(2)
 }
 
-impl Ord for Pair {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.distance().cmp(&self.distance())
-    }
+/// Every pair of junction boxes along with the (squared) distance between them
+fn pairs(x: &Input) -> Vec<(usize, usize, i64)> {
+    x.iter()
+        .enumerate()
+        .tuple_combinations()
+        .map(|((a, box_a), (b, box_b))| (a, b, distance(box_a, box_b)))
+        .collect()
 }
 
-fn problem1(x: &Input, pairs: usize) -> u64 {
-    let mut circuits = x.clone();
-    let mut queue =
-        BinaryHeap::from_iter(circuits.keys().combinations(2).map(|x| Pair(*x[0], *x[1])));
-
-    let mut count = 0;
-    while let Some(Pair(a, b)) = queue.pop() {
-        count += 1;
-        if count > pairs {
-            break;
-        }
+fn problem1(x: &Input, pairs_to_connect: usize) -> u64 {
+    let mut pairs = pairs(x);
+    pairs.sort_by_key(|&(_, _, distance)| distance);
 
-        match (circuits.get(&a).cloned(), circuits.get(&b).cloned()) {
-            (Some(a_id), Some(b_id)) if a_id != b_id => {
-                circuits
-                    .values_mut()
-                    .filter(|x| **x == b_id)
-                    .for_each(|x| *x = a_id);
-            }
-            _ => {}
-        }
+    let mut circuits = UnionFind::new(x.len());
+    for &(a, b, _) in pairs.iter().take(pairs_to_connect) {
+        circuits.union(a, b);
     }
 
-    let grouped = circuits.values().counts();
-    let largest: u64 = grouped
-        .values()
+    let largest: u64 = circuits
+        .components()
+        .map(|c| c.len() as u64)
         .sorted()
         .rev()
         .take(3)
-        .map(|x| *x as u64)
         .product();
 
     largest
 }
 
 fn problem2(x: &Input) -> u64 {
-    let mut circuits = x.clone();
-    let mut queue =
-        BinaryHeap::from_iter(circuits.keys().combinations(2).map(|x| Pair(*x[0], *x[1])));
+    // the last connection of the minimum spanning tree is the one that joins everything into a single circuit
+    let tree = kruskal(x.len(), pairs(x));
+    let &(a, b, _) = tree.last().unwrap();
 
-    while let Some(Pair(a, b)) = queue.pop() {
-        match (circuits.get(&a).cloned(), circuits.get(&b).cloned()) {
-            (Some(a_id), Some(b_id)) if a_id != b_id => {
-                circuits
-                    .values_mut()
-                    .filter(|x| **x == b_id)
-                    .for_each(|x| *x = a_id);
-
-                if circuits.values().unique().count() == 1 {
-                    return (a.0 * b.0) as u64;
-                }
-            }
-            _ => {}
-        }
-    }
-    unreachable!()
+    (x[a].0 * x[b].0) as u64
 }
 
 #[cfg(test)]
@@ -1,6 +1,10 @@
 use std::collections::BinaryHeap;
 
-use common::{answer, read_input};
+use common::{
+    answer,
+    linalg::{minimize_integer, Matrix, Rational},
+    read_input,
+};
 use itertools::Itertools;
 use nom::{
     branch::alt,
@@ -11,7 +15,6 @@ use nom::{
     sequence::{delimited, preceded, terminated},
     IResult, Parser,
 };
-use z3::{ast::Int, Optimize, SatResult};
 
 fn main() {
     let input = read_input!();
@@ -52,58 +55,28 @@ impl Ord for State {
 
 impl MachineSpec {
     fn configure_joltage(&self) -> u64 {
-        let solution = Optimize::new();
-
-        let button_presses = self
-            .button_groups
-            .iter()
-            .enumerate()
-            .map(|(idx, _x)| Int::fresh_const(&format!("button_{idx}")))
+        // one row per counter, saying how much each button adds to it
+        let counters = (0..self.joltage.len())
+            .map(|target_idx| {
+                self.button_groups
+                    .iter()
+                    .map(|button| button.contains(&target_idx) as i64)
+                    .collect_vec()
+            })
             .collect_vec();
 
-        let joltage_constraints = self
+        let targets = self
             .joltage
             .iter()
-            .enumerate()
-            .map(|(target_idx, &target)| {
-                let terms = self
-                    .button_groups
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(button_idx, button)| {
-                        button
-                            .contains(&target_idx)
-                            .then_some(button_presses[button_idx].clone())
-                    })
-                    .collect_vec();
-
-                let sum = Int::add(&terms);
-                let target = Int::from_u64(target);
-
-                sum.eq(target)
-            })
+            .map(|&x| Rational::from(x as i64))
             .collect_vec();
+        let costs = vec![Rational::ONE; self.button_groups.len()];
 
-        // now add the constraints
-        for x in &button_presses {
-            solution.assert(&x.ge(0));
-        }
-
-        for x in joltage_constraints {
-            solution.assert(&x);
-        }
-
-        let total = Int::fresh_const("total");
-        solution.assert(&total.eq(Int::add(&button_presses)));
-        solution.minimize(&total);
+        let (total, _presses) =
+            minimize_integer(&Matrix::from_integers(&counters), &targets, &costs)
+                .expect("the joltage should be reachable");
 
-        assert_eq!(solution.check(&[]), SatResult::Sat);
-
-        solution
-            .get_model()
-            .and_then(|m| m.eval(&total, true))
-            .and_then(|x| x.as_u64())
-            .unwrap()
+        total.to_integer().unwrap() as u64
     }
 
     fn configure_lights(&self) -> u64 {
@@ -165,12 +138,7 @@ fn parse(input: &str) -> Input {
                 tag(")"),
             ),
         ),
-        |x| {
-            (
-                x.iter().cloned().map(|x| x.0).collect_vec(),
-                x.iter().cloned().map(|x| x.1).collect_vec(),
-            )
-        },
+        |x| x.into_iter().unzip(),
     );
     let joltage = delimited(tag("{"), separated_list1(tag(","), u64), tag("}"));
 
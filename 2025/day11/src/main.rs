@@ -1,6 +1,6 @@
 use std::{collections::HashMap, hash::Hash};
 
-use common::{answer, read_input};
+use common::{answer, memo::Memo, read_input};
 use nom::{
     bytes::complete::tag,
     character::{complete::newline, streaming::alpha1},
@@ -62,7 +62,7 @@ fn paths<'a, F>(
     state: State<'a>,
     to: &'a str,
     x: &'a Input,
-    cache: &mut HashMap<State<'a>, u64>,
+    cache: &mut Memo<State<'a>, u64>,
     f: F,
 ) -> u64
 where
@@ -72,29 +72,21 @@ where
         return f(&state).into();
     }
 
-    let result = match cache.get(&state) {
-        Some(x) => *x,
-        None => {
-            let result = x
-                .get(state.node)
-                .unwrap_or(&vec![])
-                .iter()
-                .map(|n| paths(state.move_to(n), to, x, cache, f))
-                .sum();
-            cache.insert(state, result);
-            result
-        }
-    };
-
-    result
+    cache.get_or_compute(state.clone(), |cache| {
+        x.get(state.node)
+            .unwrap_or(&vec![])
+            .iter()
+            .map(|n| paths(state.move_to(n), to, x, cache, f))
+            .sum()
+    })
 }
 
 fn problem1(x: &Input) -> u64 {
-    paths(State::new("you"), "out", x, &mut HashMap::new(), |_| true)
+    paths(State::new("you"), "out", x, &mut Memo::new(), |_| true)
 }
 
 fn problem2(x: &Input) -> u64 {
-    paths(State::new("svr"), "out", x, &mut HashMap::new(), |s| {
+    paths(State::new("svr"), "out", x, &mut Memo::new(), |s| {
         s.dac && s.fft
     })
 }
@@ -0,0 +1,79 @@
+use std::hint::black_box;
+
+use common::{fastparse, nom::integers, nom::parse_grid};
+use criterion::{criterion_group, criterion_main, Criterion};
+use nom::{character::complete::none_of, Parser};
+
+// about the size of the biggest real inputs, which are a few hundred KB
+fn number_lines() -> String {
+    (0..20_000i64)
+        .map(|i| {
+            format!(
+                "p=<{},{}> v={} -> {}\n",
+                i * 7919 % 1000 - 500,
+                i,
+                -i,
+                i * i
+            )
+        })
+        .collect()
+}
+
+fn square_grid() -> String {
+    (0..500)
+        .map(|y| {
+            (0..500)
+                .map(|x| if (x * y) % 7 == 0 { '#' } else { '.' })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
+
+fn numbers(c: &mut Criterion) {
+    let input = number_lines();
+    let mut group = c.benchmark_group("numbers");
+
+    group.bench_function("nom", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| {
+                    integers::<i64, nom::error::Error<_>>(line)
+                        .unwrap()
+                        .1
+                        .into_iter()
+                        .sum::<i64>()
+                })
+                .sum::<i64>()
+        })
+    });
+    group.bench_function("fastparse", |b| {
+        b.iter(|| fastparse::integers::<i64>(black_box(input.as_bytes())).sum::<i64>())
+    });
+
+    group.finish();
+}
+
+fn grids(c: &mut Criterion) {
+    let input = square_grid();
+    let mut group = c.benchmark_group("grid");
+
+    group.bench_function("nom", |b| {
+        b.iter(|| {
+            let grid = parse_grid(none_of::<_, _, nom::error::Error<_>>("\n"))
+                .parse(black_box(input.as_str()))
+                .unwrap()
+                .1;
+            grid.width
+        })
+    });
+    group.bench_function("fastparse", |b| {
+        b.iter(|| fastparse::grid(black_box(input.as_bytes()), |b| b == b'#').width)
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, numbers, grids);
+criterion_main!(benches);
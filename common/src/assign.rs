@@ -0,0 +1,257 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use crate::graph::{find_cycle, Interner};
+
+/// Why an assignment couldn't be pinned down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignError<K> {
+    /// There aren't enough values to go around, and these keys were left without one
+    Infeasible(Vec<K>),
+    /// There's more than one valid assignment, and these keys can be given different values
+    Ambiguous(Vec<K>),
+}
+
+/// Give every key a different value out of its candidates, for puzzles like "which column is which field". A key
+/// that shows up more than once has to satisfy all of its candidate sets, so only the values they have in common
+/// are kept.
+///
+/// Keys with only one candidate left are assigned first, and their value is crossed off everywhere else. If that
+/// stalls, a maximum matching of what's left decides whether there's no solution or more than one.
+pub fn assign<K, V, I>(
+    candidates: impl IntoIterator<Item = (K, I)>,
+) -> Result<HashMap<K, V>, AssignError<K>>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + Eq + Hash,
+    I: IntoIterator<Item = V>,
+{
+    let mut keys = Interner::new();
+    let mut values = Interner::new();
+    let mut sets: Vec<HashSet<usize>> = vec![];
+    for (key, options) in candidates {
+        let k = keys.intern(key);
+        let options: HashSet<usize> = options.into_iter().map(|v| values.intern(v)).collect();
+        if k == sets.len() {
+            sets.push(options);
+        } else {
+            sets[k].retain(|v| options.contains(v));
+        }
+    }
+
+    let label = |ks: &[usize]| ks.iter().map(|&k| keys.label(k).clone()).collect();
+
+    if let Some(k) = sets.iter().position(|s| s.is_empty()) {
+        return Err(AssignError::Infeasible(label(&[k])));
+    }
+
+    // naked singles: a key with one option has to take it, which might leave other keys with only one option
+    let mut assigned: Vec<Option<usize>> = vec![None; sets.len()];
+    let mut singles: Vec<usize> = (0..sets.len()).filter(|&k| sets[k].len() == 1).collect();
+    while let Some(k) = singles.pop() {
+        let v = *sets[k].iter().next().unwrap();
+        assigned[k] = Some(v);
+
+        for other in (0..sets.len()).filter(|&o| o != k && assigned[o].is_none()) {
+            if sets[other].remove(&v) {
+                match sets[other].len() {
+                    0 => return Err(AssignError::Infeasible(label(&[other]))),
+                    1 => singles.push(other),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let remaining: Vec<usize> = (0..sets.len()).filter(|&k| assigned[k].is_none()).collect();
+    if !remaining.is_empty() {
+        let adj: Vec<Vec<usize>> = remaining
+            .iter()
+            .map(|&k| {
+                let mut options: Vec<usize> = sets[k].iter().copied().collect();
+                options.sort();
+                options
+            })
+            .collect();
+
+        let matching = hopcroft_karp(&adj, values.len());
+        let unmatched: Vec<usize> = (0..remaining.len())
+            .filter(|&i| matching[i].is_none())
+            .map(|i| remaining[i])
+            .collect();
+        if !unmatched.is_empty() {
+            return Err(AssignError::Infeasible(label(&unmatched)));
+        }
+
+        let mut owner = vec![None; values.len()];
+        for (i, v) in matching.iter().enumerate() {
+            owner[v.unwrap()] = Some(i);
+        }
+
+        // the matching is the only one unless some key could switch to a value nobody is using...
+        if let Some(i) = (0..adj.len()).find(|&i| adj[i].iter().any(|&v| owner[v].is_none())) {
+            return Err(AssignError::Ambiguous(label(&[remaining[i]])));
+        }
+
+        // ...or a group of keys could all take the next one's value round in a circle
+        let swaps: Vec<Vec<usize>> = adj
+            .iter()
+            .enumerate()
+            .map(|(i, options)| {
+                options
+                    .iter()
+                    .filter_map(|&v| owner[v])
+                    .filter(|&j| j != i)
+                    .collect()
+            })
+            .collect();
+        if let Some(cycle) = find_cycle(&swaps) {
+            let cycle: Vec<usize> = cycle.0.iter().map(|&i| remaining[i]).collect();
+            return Err(AssignError::Ambiguous(label(&cycle)));
+        }
+
+        for (i, v) in matching.into_iter().enumerate() {
+            assigned[remaining[i]] = v;
+        }
+    }
+
+    Ok(assigned
+        .into_iter()
+        .enumerate()
+        .map(|(k, v)| (keys.label(k).clone(), values.label(v.unwrap()).clone()))
+        .collect())
+}
+
+/// Hopcroft–Karp maximum bipartite matching. `adj[u]` lists the right hand nodes (`0..right`) that left hand node
+/// `u` can be matched with, and the result is what each left hand node ended up matched to.
+pub fn hopcroft_karp(adj: &[Vec<usize>], right: usize) -> Vec<Option<usize>> {
+    let mut left_match = vec![None; adj.len()];
+    let mut right_match = vec![None; right];
+
+    loop {
+        // layer the graph by the length of the shortest alternating path from an unmatched left node
+        let mut dist = vec![usize::MAX; adj.len()];
+        let mut queue = VecDeque::new();
+        for u in (0..adj.len()).filter(|&u| left_match[u].is_none()) {
+            dist[u] = 0;
+            queue.push_back(u);
+        }
+
+        let mut found = false;
+        while let Some(u) = queue.pop_front() {
+            for &v in &adj[u] {
+                match right_match[v] {
+                    None => found = true,
+                    Some(w) if dist[w] == usize::MAX => {
+                        dist[w] = dist[u] + 1;
+                        queue.push_back(w);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !found {
+            break;
+        }
+
+        for u in 0..adj.len() {
+            if left_match[u].is_none() {
+                augment(adj, u, &mut dist, &mut left_match, &mut right_match);
+            }
+        }
+    }
+
+    left_match
+}
+
+/// Look for an augmenting path from `u` that follows the layers, flipping every edge along it if we find one
+fn augment(
+    adj: &[Vec<usize>],
+    u: usize,
+    dist: &mut [usize],
+    left_match: &mut [Option<usize>],
+    right_match: &mut [Option<usize>],
+) -> bool {
+    for &v in &adj[u] {
+        let free = match right_match[v] {
+            None => true,
+            Some(w) => dist[w] == dist[u] + 1 && augment(adj, w, dist, left_match, right_match),
+        };
+
+        if free {
+            left_match[u] = Some(v);
+            right_match[v] = Some(u);
+            return true;
+        }
+    }
+
+    // nothing useful down here, so don't come back this phase
+    dist[u] = usize::MAX;
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{assign, hopcroft_karp, AssignError};
+
+    #[test]
+    fn propagation() {
+        let candidates = [
+            ("row", vec![0, 1, 2]),
+            ("class", vec![1, 2]),
+            ("seat", vec![2]),
+            // repeated keys narrow down the options
+            ("row", vec![0, 1]),
+        ];
+        let expected = HashMap::from([("row", 0), ("class", 1), ("seat", 2)]);
+        assert_eq!(assign(candidates), Ok(expected));
+    }
+
+    #[test]
+    fn errors() {
+        let infeasible = [("a", vec![1]), ("b", vec![1, 2]), ("c", vec![2])];
+        assert!(matches!(
+            assign(infeasible),
+            Err(AssignError::Infeasible(_))
+        ));
+
+        // nothing has a single option, and there aren't enough values for the first three keys
+        let stalled = [
+            ("a", vec![1, 2]),
+            ("b", vec![1, 2]),
+            ("c", vec![1, 2]),
+            ("d", vec![3, 4]),
+        ];
+        let Err(AssignError::Infeasible(keys)) = assign(stalled) else {
+            panic!("expected no solution");
+        };
+        assert_eq!(keys.len(), 1);
+        assert_ne!(keys, ["d"]);
+
+        let ambiguous = [("a", vec![1, 2]), ("b", vec![1, 2]), ("c", vec![3])];
+        let Err(AssignError::Ambiguous(mut keys)) = assign(ambiguous) else {
+            panic!("expected more than one solution");
+        };
+        keys.sort();
+        assert_eq!(keys, ["a", "b"]);
+
+        // there's a spare value that either key could use
+        let spare = [("a", vec![1, 2, 3]), ("b", vec![1, 2, 3]), ("c", vec![1])];
+        assert!(matches!(assign(spare), Err(AssignError::Ambiguous(_))));
+    }
+
+    #[test]
+    fn matching() {
+        // the greedy choice of 0 for the first node has to be undone to fit the second one in
+        let adj = [vec![0, 1], vec![0], vec![1, 2]];
+        assert_eq!(hopcroft_karp(&adj, 3), [Some(1), Some(0), Some(2)]);
+
+        let adj = [vec![0], vec![0], vec![1]];
+        assert_eq!(hopcroft_karp(&adj, 2).iter().flatten().count(), 2);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states starts repeating. The states at `start` and `start + length` are the same, and
+/// everything from there on repeats every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Period {
+    /// The earliest step that's in the same state as step `n`
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+// https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm
+/// Brent's algorithm. Only keeps a couple of states around at a time, so it works for states that are expensive to
+/// store, but it needs to compare whole states and runs the simulation a few times over.
+pub fn brent<S, F>(initial: &S, mut step: F) -> Period
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // find the cycle length by teleporting the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then find the start by walking two states `length` apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Period { start, length }
+}
+
+/// Find the period by remembering a `fingerprint` of every state we've seen. This only runs the simulation once,
+/// and the fingerprint can be much smaller than the state itself (e.g. the top few rows of a tower)
+pub fn find_period<S, K, F, FK>(initial: S, mut step: F, mut fingerprint: FK) -> Period
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    FK: FnMut(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut state = initial;
+
+    for n in 0.. {
+        if let Some(start) = seen.insert(fingerprint(&state), n) {
+            return Period {
+                start,
+                length: n - start,
+            };
+        }
+        state = step(&state);
+    }
+
+    unreachable!("ran out of steps before finding a cycle")
+}
+
+/// The state after `n` steps, using Brent's algorithm to skip over all the repeats
+pub fn nth_state<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let period = brent(&initial, &mut step);
+    advance(initial, step, period.index_of(n))
+}
+
+/// The state after `n` steps, finding the period with `find_period`
+pub fn nth_state_by_key<S, K, F, FK>(initial: S, mut step: F, fingerprint: FK, n: usize) -> S
+where
+    S: Clone,
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    FK: FnMut(&S) -> K,
+{
+    let period = find_period(initial.clone(), &mut step, fingerprint);
+    advance(initial, step, period.index_of(n))
+}
+
+/// The value of `metric` after `n` steps for a metric that keeps growing even though the state repeats (like the
+/// height of a tower where the top keeps looking the same). Whatever the metric gained over one trip around the
+/// cycle gets added again for every cycle we skip.
+pub fn project<S, K, F, FK, FM>(
+    initial: S,
+    mut step: F,
+    mut fingerprint: FK,
+    mut metric: FM,
+    n: usize,
+) -> i64
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> S,
+    FK: FnMut(&S) -> K,
+    FM: FnMut(&S) -> i64,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut values = vec![];
+    let mut state = initial;
+
+    for i in 0..=n {
+        values.push(metric(&state));
+        if let Some(start) = seen.insert(fingerprint(&state), i) {
+            let period = Period {
+                start,
+                length: i - start,
+            };
+
+            let cycles = ((n - start) / period.length) as i64;
+            let delta = values[i] - values[start];
+            return values[period.index_of(n)] + cycles * delta;
+        }
+        state = step(&state);
+    }
+
+    // we got to n before anything repeated
+    values[n]
+}
+
+fn advance<S, F>(mut state: S, mut step: F, n: usize) -> S
+where
+    F: FnMut(&S) -> S,
+{
+    for _ in 0..n {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::{brent, find_period, nth_state, nth_state_by_key, project, Period};
+
+    // 3 -> 10 -> 5 -> 16 -> 8 -> 4 -> 2 -> 1 -> 4 -> ...
+    fn collatz(n: &u64) -> u64 {
+        if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        }
+    }
+
+    #[test]
+    fn periods() {
+        let expected = Period {
+            start: 5,
+            length: 3,
+        };
+        assert_eq!(brent(&3, collatz), expected);
+        assert_eq!(find_period(3, collatz, |&n| n), expected);
+    }
+
+    #[test]
+    fn nth() {
+        assert_eq!(nth_state(3, collatz, 4), 8);
+        assert_eq!(nth_state(3, collatz, 1_000_000_000), 1);
+        assert_eq!(nth_state_by_key(3, collatz, |&n| n, 1_000_000_001), 4);
+    }
+
+    #[test]
+    fn projection() {
+        // the position on a circle repeats every 7 steps, but the distance travelled keeps going up by 3 per step
+        let step = |&(position, travelled): &(u64, i64)| ((position + 3) % 7, travelled + 3);
+        let result = project((0, 0), step, |&(position, _)| position, |s| s.1, 1_000_000);
+        assert_eq!(result, 3_000_000);
+        assert_eq!(project((0, 0), step, |s| s.0, |s| s.1, 5), 15);
+    }
+}
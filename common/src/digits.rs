@@ -0,0 +1,216 @@
+use crate::math::Integer;
+
+/// A number base, for working with the digits of a number without going through a string. The free functions in
+/// this module are the same thing in base 10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base(pub u32);
+
+pub const DECIMAL: Base = Base(10);
+
+impl Base {
+    fn radix(self) -> u128 {
+        assert!(self.0 >= 2, "base {} doesn't make sense", self.0);
+        self.0 as u128
+    }
+
+    /// How many digits `n` has (ignoring the sign), where zero has one digit
+    pub fn digit_count<T: Integer>(self, n: T) -> u32 {
+        let n = n.magnitude();
+        if n == 0 {
+            1
+        } else {
+            n.ilog(self.radix()) + 1
+        }
+    }
+
+    /// The digits of `n` (ignoring the sign), most significant first. Use `rev` to go from the least significant.
+    pub fn digits<T: Integer>(self, n: T) -> Digits {
+        let count = self.digit_count(n);
+        Digits {
+            value: n.magnitude(),
+            radix: self.radix(),
+            high: self.radix().pow(count - 1),
+            len: count as usize,
+        }
+    }
+
+    /// Split off the last `k` digits, so `split_at_digit(12345, 2)` is `(123, 45)`
+    pub fn split_at_digit<T: Integer>(self, n: T, k: u32) -> (T, T) {
+        match self.radix().checked_pow(k).and_then(T::from_u128) {
+            Some(p) => (n / p, n % p),
+            None => (T::ZERO, n),
+        }
+    }
+
+    /// The digits of `b` written after the digits of `a`, or `None` if that doesn't fit
+    pub fn concat<T: Integer>(self, a: T, b: T) -> Option<T> {
+        let shift = T::from_u128(self.radix().checked_pow(self.digit_count(b))?)?;
+        a.checked_mul(shift)?.checked_add(b)
+    }
+
+    /// The number made of the digits, most significant first. `None` if a digit is too big for the base or the
+    /// number doesn't fit.
+    pub fn from_digits<T: Integer, D: Integer>(
+        self,
+        digits: impl IntoIterator<Item = D>,
+    ) -> Option<T> {
+        let radix = T::from_u128(self.radix());
+        digits.into_iter().try_fold(T::ZERO, |acc, d| {
+            if d.is_negative() || d.magnitude() >= self.radix() {
+                return None;
+            }
+            acc.checked_mul(radix?)?
+                .checked_add(T::from_u128(d.magnitude())?)
+        })
+    }
+
+    /// Whether the digits of `n` are the same block of digits written out exactly `times` times, like 123123
+    pub fn repeats<T: Integer>(self, n: T, times: u32) -> bool {
+        let count = self.digit_count(n);
+        if times == 0 || !count.is_multiple_of(times) {
+            return false;
+        }
+
+        let width = self.radix().pow(count / times);
+        let block = n.magnitude() % width;
+        let repeated = (0..times).fold(0, |acc, _| acc * width + block);
+        repeated == n.magnitude()
+    }
+
+    /// The length of the shortest block of digits that repeats (at least twice) to make `n`, if there is one
+    pub fn repeating_pattern<T: Integer>(self, n: T) -> Option<u32> {
+        let count = self.digit_count(n);
+        (1..=count / 2).find(|&k| count.is_multiple_of(k) && self.repeats(n, count / k))
+    }
+}
+
+/// An iterator over the digits of a number, from either end
+#[derive(Debug, Clone)]
+pub struct Digits {
+    value: u128,
+    radix: u128,
+    high: u128,
+    len: usize,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.value / self.high;
+        self.value %= self.high;
+        self.high /= self.radix;
+        self.len -= 1;
+        Some(digit as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.value % self.radix;
+        self.value /= self.radix;
+        self.high /= self.radix;
+        self.len -= 1;
+        Some(digit as u8)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+pub fn digit_count<T: Integer>(n: T) -> u32 {
+    DECIMAL.digit_count(n)
+}
+
+pub fn digits<T: Integer>(n: T) -> Digits {
+    DECIMAL.digits(n)
+}
+
+pub fn split_at_digit<T: Integer>(n: T, k: u32) -> (T, T) {
+    DECIMAL.split_at_digit(n, k)
+}
+
+pub fn concat<T: Integer>(a: T, b: T) -> Option<T> {
+    DECIMAL.concat(a, b)
+}
+
+pub fn from_digits<T: Integer, D: Integer>(digits: impl IntoIterator<Item = D>) -> Option<T> {
+    DECIMAL.from_digits(digits)
+}
+
+pub fn repeats<T: Integer>(n: T, times: u32) -> bool {
+    DECIMAL.repeats(n, times)
+}
+
+pub fn repeating_pattern<T: Integer>(n: T) -> Option<u32> {
+    DECIMAL.repeating_pattern(n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        concat, digit_count, digits, from_digits, repeating_pattern, repeats, split_at_digit, Base,
+    };
+
+    #[test]
+    fn counting_and_splitting() {
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(999u64), 3);
+        assert_eq!(digit_count(1000u64), 4);
+        assert_eq!(digit_count(-42), 2);
+        assert_eq!(digit_count(u128::MAX), 39);
+
+        assert_eq!(split_at_digit(12345, 2), (123, 45));
+        assert_eq!(split_at_digit(12u8, 5), (0, 12));
+
+        assert_eq!(concat(12u64, 345), Some(12345));
+        assert_eq!(concat(1u64, 10), Some(110));
+        assert_eq!(concat(7, 0), Some(70));
+        assert_eq!(concat(u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn iterating() {
+        assert_eq!(digits(1203).collect::<Vec<_>>(), [1, 2, 0, 3]);
+        assert_eq!(digits(1203).rev().collect::<Vec<_>>(), [3, 0, 2, 1]);
+        assert_eq!(digits(0).collect::<Vec<_>>(), [0]);
+
+        // taking from both ends meets in the middle
+        let mut d = digits(12345);
+        assert_eq!((d.next(), d.next_back(), d.len()), (Some(1), Some(5), 3));
+        assert_eq!(d.collect::<Vec<_>>(), [2, 3, 4]);
+
+        let binary = Base(2);
+        assert_eq!(binary.digits(10).collect::<Vec<_>>(), [1, 0, 1, 0]);
+        assert_eq!(binary.from_digits([1u8, 1, 0]), Some(6));
+        assert_eq!(Base(16).digit_count(255), 2);
+
+        assert_eq!(from_digits::<u32, _>([4u32, 0, 2]), Some(402));
+        assert_eq!(from_digits::<u8, _>([2u8, 5, 6]), None);
+        assert_eq!(from_digits::<u64, _>([1, 10]), None);
+    }
+
+    #[test]
+    fn patterns() {
+        assert!(repeats(123123, 2));
+        assert!(repeats(1111, 2));
+        assert!(!repeats(1231234, 2));
+        assert!(!repeats(101, 2));
+
+        assert_eq!(repeating_pattern(121212), Some(2));
+        assert_eq!(repeating_pattern(1111111), Some(1));
+        assert_eq!(repeating_pattern(1212123), None);
+        assert_eq!(repeating_pattern(7), None);
+    }
+}
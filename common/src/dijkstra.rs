@@ -1,6 +1,6 @@
 use std::{
     cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
+    collections::{BinaryHeap, HashMap, VecDeque},
 };
 
 use crate::grid::GridSquare;
@@ -83,6 +83,121 @@ pub fn shortest_path(adj_list: &[Vec<Edge>], start: GridIndex, goal: GridIndex)
     None
 }
 
+/// Shortest distance from `start` to every node in the graph, or `None` for nodes that can't be reached
+pub fn distances_from(adj_list: &[Vec<Edge>], start: GridIndex) -> Vec<Option<usize>> {
+    let dist = distances(adj_list, start, |_| false);
+    dist.into_iter()
+        .map(|d| (d != usize::MAX).then_some(d))
+        .collect()
+}
+
+/// Shortest distance from `start` to each of the `goals`, in the same order. Stops searching once every goal has
+/// been reached instead of exploring the whole graph.
+pub fn distances_to(
+    adj_list: &[Vec<Edge>],
+    start: GridIndex,
+    goals: &[GridIndex],
+) -> Vec<Option<usize>> {
+    let mut remaining = vec![false; adj_list.len()];
+    for &goal in goals {
+        remaining[goal] = true;
+    }
+    let mut count = remaining.iter().filter(|&&x| x).count();
+
+    let dist = distances(adj_list, start, |position| {
+        if remaining[position] {
+            remaining[position] = false;
+            count -= 1;
+        }
+        count == 0
+    });
+
+    goals
+        .iter()
+        .map(|&goal| (dist[goal] != usize::MAX).then_some(dist[goal]))
+        .collect()
+}
+
+/// Shortest distance between every pair of `nodes`. `result[i][j]` is the distance from `nodes[i]` to `nodes[j]`.
+pub fn all_pairs(adj_list: &[Vec<Edge>], nodes: &[GridIndex]) -> Vec<Vec<Option<usize>>> {
+    nodes
+        .iter()
+        .map(|&start| distances_to(adj_list, start, nodes))
+        .collect()
+}
+
+/// Same as `distances_from` for graphs where every edge costs 0 or 1 (most grid mazes), using a deque instead of
+/// a heap. Panics if any edge costs more than 1.
+pub fn distances_from_01(adj_list: &[Vec<Edge>], start: GridIndex) -> Vec<Option<usize>> {
+    let mut dist = vec![usize::MAX; adj_list.len()];
+    let mut queue = VecDeque::new();
+
+    dist[start] = 0;
+    queue.push_back(start);
+
+    while let Some(position) = queue.pop_front() {
+        for edge in &adj_list[position] {
+            assert!(edge.cost <= 1, "edge cost {} is not 0 or 1", edge.cost);
+
+            let cost = dist[position] + edge.cost;
+            if cost < dist[edge.node] {
+                dist[edge.node] = cost;
+
+                // free edges go to the front so we finish everything at this distance first
+                if edge.cost == 0 {
+                    queue.push_front(edge.node);
+                } else {
+                    queue.push_back(edge.node);
+                }
+            }
+        }
+    }
+
+    dist.into_iter()
+        .map(|d| (d != usize::MAX).then_some(d))
+        .collect()
+}
+
+/// Dijkstra from `start` that runs until `done` returns true for a settled node or the graph is exhausted.
+/// Unreachable nodes are left at `usize::MAX`.
+fn distances<F>(adj_list: &[Vec<Edge>], start: GridIndex, mut done: F) -> Vec<usize>
+where
+    F: FnMut(GridIndex) -> bool,
+{
+    let mut dist = vec![usize::MAX; adj_list.len()];
+    let mut heap = BinaryHeap::new();
+
+    dist[start] = 0;
+    heap.push(State {
+        cost: 0,
+        position: start,
+    });
+
+    while let Some(State { cost, position }) = heap.pop() {
+        if cost > dist[position] {
+            continue;
+        }
+
+        if done(position) {
+            break;
+        }
+
+        for edge in &adj_list[position] {
+            let next = State {
+                cost: cost + edge.cost,
+                position: edge.node,
+            };
+
+            if next.cost < dist[next.position] {
+                heap.push(next);
+                dist[next.position] = next.cost;
+            }
+        }
+    }
+
+    dist
+}
+
 pub fn connected_components(input: &[Vec<Edge>]) -> HashMap<usize, Vec<usize>> {
     let mut visited = vec![false; input.len()];
     let mut groups = HashMap::new();
@@ -112,3 +227,60 @@ pub fn connected_components(input: &[Vec<Edge>]) -> HashMap<usize, Vec<usize>> {
 
     groups
 }
+
+#[cfg(test)]
+mod test {
+    use super::{all_pairs, distances_from, distances_from_01, distances_to, shortest_path, Edge};
+
+    // 0 -1- 1 -1- 2 -1- 3, with a 0 -5- 3 shortcut that isn't actually shorter, and 4 off on its own
+    fn graph() -> Vec<Vec<Edge>> {
+        let edges = [(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 5)];
+        let mut adj_list = vec![vec![]; 5];
+        for (a, b, cost) in edges {
+            adj_list[a].push(Edge { node: b, cost });
+            adj_list[b].push(Edge { node: a, cost });
+        }
+        adj_list
+    }
+
+    #[test]
+    fn distances() {
+        let adj_list = graph();
+        let expected = vec![Some(0), Some(1), Some(2), Some(3), None];
+        assert_eq!(distances_from(&adj_list, 0), expected);
+        assert_eq!(
+            distances_to(&adj_list, 0, &[3, 4, 1]),
+            vec![Some(3), None, Some(1)]
+        );
+        assert_eq!(shortest_path(&adj_list, 0, 3), Some(3));
+    }
+
+    #[test]
+    fn pairs() {
+        let adj_list = graph();
+        let pairs = all_pairs(&adj_list, &[0, 2, 3]);
+        assert_eq!(
+            pairs,
+            vec![
+                vec![Some(0), Some(2), Some(3)],
+                vec![Some(2), Some(0), Some(1)],
+                vec![Some(3), Some(1), Some(0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_one() {
+        // 0 -0- 1 -1- 2 -0- 3
+        let adj_list = vec![
+            vec![Edge { node: 1, cost: 0 }],
+            vec![Edge { node: 2, cost: 1 }],
+            vec![Edge { node: 3, cost: 0 }],
+            vec![],
+            vec![],
+        ];
+
+        let expected = vec![Some(0), Some(0), Some(1), Some(1), None];
+        assert_eq!(distances_from_01(&adj_list, 0), expected);
+    }
+}
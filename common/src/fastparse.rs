@@ -0,0 +1,212 @@
+use std::{any::type_name, marker::PhantomData};
+
+use memchr::Memchr;
+
+use crate::{grid::Grid, math::Integer};
+
+/// The lines of the input, without their line endings. Finding the newlines is vectorised by `memchr`, which is a
+/// lot quicker than looking at one character at a time. Like `str::lines`, a newline at the very end doesn't make
+/// an extra empty line.
+pub fn lines(input: &[u8]) -> Lines<'_> {
+    Lines {
+        input,
+        newlines: memchr::memchr_iter(b'\n', input),
+        start: 0,
+    }
+}
+
+pub struct Lines<'a> {
+    input: &'a [u8],
+    newlines: Memchr<'a>,
+    start: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = match self.newlines.next() {
+            Some(end) => end,
+            None if self.start < self.input.len() => self.input.len(),
+            None => return None,
+        };
+
+        let line = &self.input[self.start..end];
+        self.start = end + 1;
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Every integer in the bytes, skipping over whatever is around them, the same as `nom::integers`. Signed types
+/// pick up a `-` right before the digits. A number that doesn't fit is a panic, where the nom version would fail.
+pub fn integers<T: Integer>(input: &[u8]) -> Integers<'_, T> {
+    Integers {
+        input,
+        position: 0,
+        integer: PhantomData,
+    }
+}
+
+pub struct Integers<'a, T> {
+    input: &'a [u8],
+    position: usize,
+    integer: PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input;
+        let start = self.position
+            + bytes[self.position..]
+                .iter()
+                .position(u8::is_ascii_digit)
+                .unwrap_or(bytes.len() - self.position);
+        if start == bytes.len() {
+            self.position = start;
+            return None;
+        }
+
+        let signed = T::ZERO.checked_sub(T::ONE).is_some();
+        let negative = signed && start > self.position && bytes[start - 1] == b'-';
+
+        // no need to check for overflow as we go, since 19 digits always fit in a u64
+        let mut end = start;
+        let mut magnitude = 0u64;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            magnitude = magnitude.wrapping_mul(10) + (bytes[end] - b'0') as u64;
+            end += 1;
+        }
+        let magnitude = if end - start <= 19 {
+            Some(magnitude as u128)
+        } else {
+            bytes[start..end].iter().try_fold(0u128, |acc, &b| {
+                acc.checked_mul(10)?.checked_add((b - b'0') as u128)
+            })
+        };
+
+        let value = magnitude.and_then(|m| {
+            if !negative || m == 0 {
+                T::from_u128(m)
+            } else {
+                // the most negative number is one further from zero than the most positive one
+                T::ZERO
+                    .checked_sub(T::from_u128(m - 1)?)?
+                    .checked_sub(T::ONE)
+            }
+        });
+        let value = value.unwrap_or_else(|| {
+            let text = String::from_utf8_lossy(&bytes[start - negative as usize..end]);
+            panic!("{text} doesn't fit in a {}", type_name::<T>())
+        });
+
+        self.position = end;
+        Some(value)
+    }
+}
+
+/// A grid with one byte per square, where `f` turns each byte into a tile. Every row has to be as wide as the first.
+pub fn grid<T>(input: &[u8], mut f: impl FnMut(u8) -> T) -> Grid<T> {
+    let mut width = None;
+    let points = lines(input)
+        .enumerate()
+        .map(|(y, row)| {
+            let width = *width.get_or_insert(row.len());
+            assert_eq!(row.len(), width, "row {y} isn't as wide as the first row");
+            row.iter().map(|&b| f(b)).collect()
+        })
+        .collect();
+
+    Grid::new(points)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use nom::{character::complete::none_of, Parser};
+
+    use super::{grid, integers, lines};
+    use crate::nom::{integers as nom_integers, parse_grid};
+
+    #[test]
+    fn scanning() {
+        let input = b"a=-12, b=+3\r\n\n10-20 x99\n";
+        assert_eq!(
+            lines(input).collect::<Vec<_>>(),
+            [&b"a=-12, b=+3"[..], b"", b"10-20 x99"]
+        );
+        assert_eq!(
+            integers::<i64>(input).collect::<Vec<_>>(),
+            [-12, 3, 10, -20, 99]
+        );
+        assert_eq!(
+            integers::<u32>(input).collect::<Vec<_>>(),
+            [12, 3, 10, 20, 99]
+        );
+
+        assert_eq!(integers::<i8>(b"-128").next(), Some(i8::MIN));
+        assert_eq!(
+            integers::<u128>(b"000000000000000000000000000123").next(),
+            Some(123)
+        );
+
+        let grid = grid(b"123\n456", |b| b - b'0');
+        assert_eq!(grid.points, [[1, 2, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "256 doesn't fit in a u8")]
+    fn overflow() {
+        integers::<u8>(b"1 256").for_each(drop);
+    }
+
+    // every test.txt in the workspace should come out the same as it does through nom
+    #[test]
+    fn matches_nom() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let mut checked = 0;
+        for year in fs::read_dir(root).unwrap().flatten() {
+            let Ok(days) = fs::read_dir(year.path()) else {
+                continue;
+            };
+
+            for day in days.flatten() {
+                let path = day.path().join("test.txt");
+                let Ok(text) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let bytes = text.as_bytes();
+                checked += 1;
+
+                assert!(lines(bytes).eq(text.lines().map(str::as_bytes)), "{path:?}");
+
+                for line in text.lines() {
+                    let expected = nom_integers::<i64, nom::error::Error<_>>(line).unwrap().1;
+                    assert_eq!(
+                        integers::<i64>(line.as_bytes()).collect::<Vec<_>>(),
+                        expected
+                    );
+                    let expected = nom_integers::<u64, nom::error::Error<_>>(line).unwrap().1;
+                    assert_eq!(
+                        integers::<u64>(line.as_bytes()).collect::<Vec<_>>(),
+                        expected
+                    );
+                }
+
+                let width = text.lines().next().map_or(0, str::len);
+                let rectangular = text.lines().all(|l| l.len() == width && l.is_ascii());
+                if width > 0 && rectangular {
+                    let expected = parse_grid(none_of::<_, _, nom::error::Error<_>>("\n"))
+                        .parse(text.as_str())
+                        .unwrap()
+                        .1;
+                    assert_eq!(grid(bytes, char::from).points, expected.points, "{path:?}");
+                }
+            }
+        }
+
+        assert!(checked > 100, "only found {checked} test inputs");
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+/// An axis-aligned box in `N` dimensions. Both corners are inclusive, so a box always has at least one point in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb<const N: usize> {
+    pub min: [i64; N],
+    pub max: [i64; N],
+}
+
+impl<const N: usize> Aabb<N> {
+    pub fn new(min: [i64; N], max: [i64; N]) -> Self {
+        assert!(
+            (0..N).all(|axis| min[axis] <= max[axis]),
+            "{min:?} isn't below {max:?} on every axis"
+        );
+        Aabb { min, max }
+    }
+
+    /// The box with any two opposite corners, in whichever order
+    pub fn from_corners(a: [i64; N], b: [i64; N]) -> Self {
+        Aabb {
+            min: std::array::from_fn(|axis| a[axis].min(b[axis])),
+            max: std::array::from_fn(|axis| a[axis].max(b[axis])),
+        }
+    }
+
+    /// How many points the box covers along one axis
+    pub fn extent(&self, axis: usize) -> u128 {
+        self.max[axis].abs_diff(self.min[axis]) as u128 + 1
+    }
+
+    pub fn volume(&self) -> u128 {
+        (0..N).map(|axis| self.extent(axis)).product()
+    }
+
+    pub fn contains(&self, point: &[i64; N]) -> bool {
+        (0..N).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// Whether all of `other` is inside this box
+    pub fn contains_box(&self, other: &Aabb<N>) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    pub fn intersects(&self, other: &Aabb<N>) -> bool {
+        (0..N).all(|axis| self.overlaps_on(other, axis))
+    }
+
+    pub fn intersection(&self, other: &Aabb<N>) -> Option<Aabb<N>> {
+        self.intersects(other).then(|| Aabb {
+            min: std::array::from_fn(|axis| self.min[axis].max(other.min[axis])),
+            max: std::array::from_fn(|axis| self.max[axis].min(other.max[axis])),
+        })
+    }
+
+    /// Whether the boxes would hit each other if one slid along `axis`, which is whether their shadows on the other
+    /// axes overlap. For things falling down, this is whether one is above the other.
+    pub fn projections_overlap(&self, other: &Aabb<N>, axis: usize) -> bool {
+        (0..N)
+            .filter(|&a| a != axis)
+            .all(|a| self.overlaps_on(other, a))
+    }
+
+    fn overlaps_on(&self, other: &Aabb<N>, axis: usize) -> bool {
+        self.min[axis] <= other.max[axis] && other.min[axis] <= self.max[axis]
+    }
+
+    /// Cut the box into the part below `at` on `axis` and the part at or above it. Either side can be empty.
+    pub fn split(&self, axis: usize, at: i64) -> (Option<Aabb<N>>, Option<Aabb<N>>) {
+        let below = (self.min[axis] < at).then(|| {
+            let mut below = *self;
+            below.max[axis] = below.max[axis].min(at - 1);
+            below
+        });
+        let above = (self.max[axis] >= at).then(|| {
+            let mut above = *self;
+            above.min[axis] = above.min[axis].max(at);
+            above
+        });
+
+        (below, above)
+    }
+
+    /// The parts of this box that aren't in `other`, as at most `2N` boxes that don't overlap
+    pub fn subtract(&self, other: &Aabb<N>) -> Vec<Aabb<N>> {
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+
+        // peel off the slabs on either side of `other` one axis at a time, and what's left in the middle is the
+        // intersection
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for axis in 0..N {
+            let (below, middle) = rest.split(axis, other.min[axis]);
+            let (middle, above) = middle.unwrap().split(axis, other.max[axis] + 1);
+            pieces.extend(below.into_iter().chain(above));
+            rest = middle.unwrap();
+        }
+
+        pieces
+    }
+
+    pub fn translate(&self, offset: [i64; N]) -> Aabb<N> {
+        Aabb {
+            min: std::array::from_fn(|axis| self.min[axis] + offset[axis]),
+            max: std::array::from_fn(|axis| self.max[axis] + offset[axis]),
+        }
+    }
+}
+
+// https://en.wikipedia.org/wiki/Inclusion–exclusion_principle
+/// The space covered by switching a sequence of boxes on and off. Every overlap is kept as its own box with a sign,
+/// so the volume is always exact no matter how the boxes cross each other.
+#[derive(Debug, Clone, Default)]
+pub struct Region<const N: usize> {
+    boxes: HashMap<Aabb<N>, i64>,
+}
+
+impl<const N: usize> Region<N> {
+    pub fn new() -> Self {
+        Region {
+            boxes: HashMap::new(),
+        }
+    }
+
+    pub fn switch(&mut self, aabb: Aabb<N>, on: bool) {
+        let mut update: HashMap<Aabb<N>, i64> = HashMap::new();
+
+        // take the box out of everything it overlaps, so it isn't counted twice
+        for (other, sign) in &self.boxes {
+            if let Some(overlap) = aabb.intersection(other) {
+                *update.entry(overlap).or_default() -= sign;
+            }
+        }
+
+        // then put it back in on its own if it's on
+        if on {
+            *update.entry(aabb).or_default() += 1;
+        }
+
+        for (aabb, change) in update {
+            let sign = self.boxes.entry(aabb).or_default();
+            *sign += change;
+            if *sign == 0 {
+                self.boxes.remove(&aabb);
+            }
+        }
+    }
+
+    pub fn volume(&self) -> u128 {
+        let total: i128 = self
+            .boxes
+            .iter()
+            .map(|(aabb, sign)| aabb.volume() as i128 * *sign as i128)
+            .sum();
+
+        total.try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Aabb, Region};
+
+    #[test]
+    fn boxes() {
+        let a = Aabb::new([0, 0, 0], [9, 9, 9]);
+        let b = Aabb::from_corners([15, 5, 5], [5, 14, 14]);
+        assert_eq!(a.volume(), 1000);
+        assert_eq!(b.extent(0), 11);
+
+        assert_eq!(a.intersection(&b), Some(Aabb::new([5, 5, 5], [9, 9, 9])));
+        assert!(a.contains(&[9, 0, 5]));
+        assert!(!a.contains_box(&b));
+        assert_eq!(a.intersection(&a.translate([10, 0, 0])), None);
+
+        // the pieces don't overlap and make up exactly the part outside b
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(|p| p.volume()).sum::<u128>(), 1000 - 125);
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(!p.intersects(&b) && a.contains_box(p));
+            assert!(pieces[i + 1..].iter().all(|q| !p.intersects(q)));
+        }
+        assert_eq!(a.subtract(&a), []);
+
+        assert_eq!(
+            a.split(1, 3),
+            (
+                Some(Aabb::new([0, 0, 0], [9, 2, 9])),
+                Some(Aabb::new([0, 3, 0], [9, 9, 9]))
+            )
+        );
+        assert_eq!(a.split(1, 0).0, None);
+
+        // stacked, but only touching in z
+        let above = Aabb::new([9, 9, 20], [12, 12, 20]);
+        assert!(a.projections_overlap(&above, 2));
+        assert!(!a.projections_overlap(&above, 0));
+    }
+
+    #[test]
+    fn switching() {
+        let mut region = Region::new();
+        region.switch(Aabb::new([10, 10, 10], [12, 12, 12]), true);
+        region.switch(Aabb::new([11, 11, 11], [13, 13, 13]), true);
+        region.switch(Aabb::new([9, 9, 9], [11, 11, 11]), false);
+        region.switch(Aabb::new([10, 10, 10], [10, 10, 10]), true);
+        assert_eq!(region.volume(), 39);
+
+        region.switch(Aabb::new([0, 0, 0], [20, 20, 20]), false);
+        assert_eq!(region.volume(), 0);
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+/// Every maximal clique in an undirected graph, using Bron–Kerbosch with pivoting. Each clique is sorted.
+pub fn maximal_cliques(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let neighbors = neighbor_sets(adj);
+    let mut cliques = vec![];
+    bron_kerbosch(
+        &neighbors,
+        &mut vec![],
+        (0..adj.len()).collect(),
+        HashSet::new(),
+        &mut cliques,
+    );
+
+    cliques
+}
+
+/// The largest clique in the graph, sorted. Ties go to the lexicographically smallest clique.
+pub fn maximum_clique(adj: &[Vec<usize>]) -> Vec<usize> {
+    maximal_cliques(adj)
+        .into_iter()
+        .max_by(|a, b| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+        .unwrap_or_default()
+}
+
+/// Every clique with exactly `k` nodes (so `k = 3` gives all the triangles), sorted within and between cliques
+pub fn k_cliques(adj: &[Vec<usize>], k: usize) -> Vec<Vec<usize>> {
+    let neighbors = neighbor_sets(adj);
+    let mut cliques = vec![];
+    extend(
+        &neighbors,
+        &mut vec![],
+        &(0..adj.len()).collect::<Vec<_>>(),
+        k,
+        &mut cliques,
+    );
+
+    cliques
+}
+
+fn neighbor_sets(adj: &[Vec<usize>]) -> Vec<HashSet<usize>> {
+    adj.iter()
+        .enumerate()
+        .map(|(v, edges)| edges.iter().copied().filter(|&u| u != v).collect())
+        .collect()
+}
+
+// https://en.wikipedia.org/wiki/Bron–Kerbosch_algorithm#With_pivoting
+fn bron_kerbosch(
+    neighbors: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if candidates.is_empty() && excluded.is_empty() {
+        let mut found = clique.clone();
+        found.sort();
+        cliques.push(found);
+        return;
+    }
+
+    // any maximal clique has to include the pivot or one of its non-neighbors, so we only need to branch on those.
+    // picking the pivot with the most candidate neighbors skips the most work
+    let pivot = *candidates
+        .union(&excluded)
+        .max_by_key(|&&u| candidates.intersection(&neighbors[u]).count())
+        .unwrap();
+
+    let branches: Vec<usize> = candidates.difference(&neighbors[pivot]).copied().collect();
+    for v in branches {
+        clique.push(v);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            candidates.intersection(&neighbors[v]).copied().collect(),
+            excluded.intersection(&neighbors[v]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+/// Grow `clique` with candidates in increasing order so each clique is only found once
+fn extend(
+    neighbors: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    candidates: &[usize],
+    k: usize,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if clique.len() == k {
+        cliques.push(clique.clone());
+        return;
+    }
+
+    for (i, &v) in candidates.iter().enumerate() {
+        let next: Vec<usize> = candidates[i + 1..]
+            .iter()
+            .copied()
+            .filter(|u| neighbors[v].contains(u))
+            .collect();
+
+        // not enough candidates left to make it to k
+        if clique.len() + 1 + next.len() < k {
+            continue;
+        }
+
+        clique.push(v);
+        extend(neighbors, clique, &next, k, cliques);
+        clique.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{k_cliques, maximal_cliques, maximum_clique};
+    use crate::graph::Interner;
+
+    // a 4-clique {a, b, c, d} with a triangle {d, e, f} hanging off one corner, and a lone edge g-h
+    fn graph() -> (Interner<&'static str>, Vec<Vec<usize>>) {
+        Interner::undirected([
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "e"),
+            ("d", "f"),
+            ("e", "f"),
+            ("g", "h"),
+        ])
+    }
+
+    #[test]
+    fn maximal() {
+        let (labels, adj) = graph();
+        let mut cliques: Vec<String> = maximal_cliques(&adj)
+            .into_iter()
+            .map(|c| c.iter().map(|&v| *labels.label(v)).collect())
+            .collect();
+        cliques.sort();
+        assert_eq!(cliques, vec!["abcd", "def", "gh"]);
+    }
+
+    #[test]
+    fn maximum() {
+        let (labels, adj) = graph();
+        let clique: Vec<&str> = maximum_clique(&adj)
+            .into_iter()
+            .map(|v| *labels.label(v))
+            .collect();
+        assert_eq!(clique, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn triangles() {
+        let (labels, adj) = graph();
+        assert_eq!(k_cliques(&adj, 3).len(), 5);
+        assert_eq!(k_cliques(&adj, 4).len(), 1);
+        assert_eq!(k_cliques(&adj, 5).len(), 0);
+        assert_eq!(labels.get(&"e"), Some(4));
+        assert_eq!(labels.get(&"z"), None);
+    }
+
+    #[test]
+    fn self_loops() {
+        let (_, adj) = Interner::undirected([("a", "a"), ("a", "b")]);
+        assert_eq!(adj, vec![vec![0, 1], vec![0]]);
+    }
+}
@@ -0,0 +1,316 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+const UNVISITED: usize = usize::MAX;
+
+/// A cycle in a directed graph. Each node has an edge to the next, and the last node has an edge back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<usize>);
+
+/// Kahn's algorithm. Whenever more than one node is ready, the one with the smallest `priority` goes next, so
+/// `|n| n` gives the lexicographically smallest ordering. Returns one of the offending cycles if there is no order.
+pub fn topological_sort<K, F>(adj: &[Vec<usize>], mut priority: F) -> Result<Vec<usize>, Cycle>
+where
+    K: Ord,
+    F: FnMut(usize) -> K,
+{
+    let mut indegree = indegrees(adj);
+    let mut available: BinaryHeap<_> = (0..adj.len())
+        .filter(|&n| indegree[n] == 0)
+        .map(|n| Reverse((priority(n), n)))
+        .collect();
+
+    let mut order = vec![];
+    while let Some(Reverse((_, n))) = available.pop() {
+        order.push(n);
+
+        for &m in &adj[n] {
+            indegree[m] -= 1;
+            if indegree[m] == 0 {
+                available.push(Reverse((priority(m), m)));
+            }
+        }
+    }
+
+    if order.len() < adj.len() {
+        return Err(find_cycle(adj).expect("graph without a topological order must have a cycle"));
+    }
+
+    Ok(order)
+}
+
+/// Tarjan's algorithm. Components are returned in reverse topological order, so if there's an edge from a node in
+/// one component to a node in another, the second component comes first.
+pub fn strongly_connected_components(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = adj.len();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next_index = 0;
+
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+
+        // do the recursion by hand so big graphs don't blow the stack. Each frame is a node and the next edge to follow
+        let mut frames = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(v, edge)) = frames.last() {
+            if let Some(&w) = adj[v].get(edge) {
+                frames.last_mut().unwrap().1 += 1;
+
+                if index[w] == UNVISITED {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    frames.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+            } else {
+                frames.pop();
+                if let Some(&(parent, _)) = frames.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+
+                // v is the root of a component, so everything above it on the stack belongs with it
+                if low[v] == index[v] {
+                    let mut component = vec![];
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// Find any cycle in the graph, including self loops
+pub fn find_cycle(adj: &[Vec<usize>]) -> Option<Cycle> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Color {
+        White,
+        Grey,
+        Black,
+    }
+
+    let mut color = vec![Color::White; adj.len()];
+
+    for root in 0..adj.len() {
+        if color[root] != Color::White {
+            continue;
+        }
+
+        // the frames are the current dfs path, so hitting a grey node means we've looped back onto it
+        let mut frames = vec![(root, 0)];
+        color[root] = Color::Grey;
+
+        while let Some(&(v, edge)) = frames.last() {
+            if let Some(&w) = adj[v].get(edge) {
+                frames.last_mut().unwrap().1 += 1;
+
+                match color[w] {
+                    Color::White => {
+                        color[w] = Color::Grey;
+                        frames.push((w, 0));
+                    }
+                    Color::Grey => {
+                        let start = frames.iter().position(|&(u, _)| u == w).unwrap();
+                        return Some(Cycle(frames[start..].iter().map(|&(u, _)| u).collect()));
+                    }
+                    Color::Black => {}
+                }
+            } else {
+                color[v] = Color::Black;
+                frames.pop();
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledJob {
+    pub node: usize,
+    pub worker: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// Every job in the order it was started
+    pub jobs: Vec<ScheduledJob>,
+    /// The time the last job finished
+    pub time: usize,
+}
+
+/// Why there's no schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// There's nobody to do any of the work
+    NoWorkers,
+    /// The dependencies go round in a circle, so some nodes can never start
+    Cycle(Cycle),
+}
+
+/// Simulate `workers` working through every node in dependency order, where node `n` takes `duration(n)` to
+/// finish. Whenever a worker is idle it picks up the available node with the smallest `priority`, and nodes
+/// become available the moment their last dependency finishes.
+pub fn schedule<K, D, P>(
+    adj: &[Vec<usize>],
+    workers: usize,
+    mut duration: D,
+    mut priority: P,
+) -> Result<Schedule, ScheduleError>
+where
+    K: Ord,
+    D: FnMut(usize) -> usize,
+    P: FnMut(usize) -> K,
+{
+    if workers == 0 {
+        return Err(ScheduleError::NoWorkers);
+    }
+
+    let mut indegree = indegrees(adj);
+    let mut available: BinaryHeap<_> = (0..adj.len())
+        .filter(|&n| indegree[n] == 0)
+        .map(|n| Reverse((priority(n), n)))
+        .collect();
+
+    // each worker is either idle or working on a node until a given time
+    let mut working: Vec<Option<(usize, usize)>> = vec![None; workers];
+    let mut jobs = vec![];
+    let mut time = 0;
+
+    loop {
+        for (worker, slot) in working.iter_mut().enumerate() {
+            if slot.is_none() {
+                if let Some(Reverse((_, node))) = available.pop() {
+                    let end = time + duration(node);
+                    *slot = Some((node, end));
+                    jobs.push(ScheduledJob {
+                        node,
+                        worker,
+                        start: time,
+                        end,
+                    });
+                }
+            }
+        }
+
+        // skip ahead to the next time a job finishes
+        let Some(next) = working.iter().flatten().map(|&(_, end)| end).min() else {
+            break;
+        };
+        time = next;
+
+        for slot in working.iter_mut() {
+            if let Some((node, end)) = *slot {
+                if end == time {
+                    *slot = None;
+                    for &m in &adj[node] {
+                        indegree[m] -= 1;
+                        if indegree[m] == 0 {
+                            available.push(Reverse((priority(m), m)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if jobs.len() < adj.len() {
+        let cycle = find_cycle(adj).expect("graph without a schedule must have a cycle");
+        return Err(ScheduleError::Cycle(cycle));
+    }
+
+    Ok(Schedule { jobs, time })
+}
+
+fn indegrees(adj: &[Vec<usize>]) -> Vec<usize> {
+    let mut indegree = vec![0; adj.len()];
+    for &m in adj.iter().flatten() {
+        indegree[m] += 1;
+    }
+    indegree
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        find_cycle, schedule, strongly_connected_components, topological_sort, Cycle, ScheduleError,
+    };
+
+    #[test]
+    fn topological_order() {
+        // 3 -> 1 -> 0, 3 -> 2 -> 0
+        let adj = vec![vec![], vec![0], vec![0], vec![1, 2]];
+        assert_eq!(topological_sort(&adj, |n| n), Ok(vec![3, 1, 2, 0]));
+        assert_eq!(
+            topological_sort(&adj, std::cmp::Reverse),
+            Ok(vec![3, 2, 1, 0])
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let adj = vec![vec![1], vec![2], vec![3], vec![1]];
+        assert_eq!(find_cycle(&adj), Some(Cycle(vec![1, 2, 3])));
+        assert_eq!(topological_sort(&adj, |n| n), Err(Cycle(vec![1, 2, 3])));
+        assert_eq!(find_cycle(&[vec![], vec![1]]), Some(Cycle(vec![1])));
+        assert_eq!(find_cycle(&[vec![1], vec![]]), None);
+    }
+
+    #[test]
+    fn components() {
+        // {0, 1, 2} -> {3, 4} -> {5}
+        let adj = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3, 5], vec![]];
+        let mut components = strongly_connected_components(&adj);
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(components, vec![vec![5], vec![3, 4], vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn scheduling() {
+        // the example from 2018 day 7, where C=2, A=0, ..., taking 1 second for A, 2 for B, etc.
+        let adj = vec![vec![1, 3], vec![4], vec![0, 5], vec![4], vec![], vec![4]];
+        let schedule = schedule(&adj, 2, |n| n + 1, |n| n).unwrap();
+        assert_eq!(schedule.time, 15);
+        let order: Vec<usize> = schedule.jobs.iter().map(|j| j.node).collect();
+        assert_eq!(order, vec![2, 0, 5, 1, 3, 4]);
+    }
+
+    #[test]
+    fn scheduling_errors() {
+        let adj = vec![vec![1], vec![]];
+        assert_eq!(
+            schedule(&adj, 0, |_| 1, |n| n),
+            Err(ScheduleError::NoWorkers)
+        );
+
+        let adj = vec![vec![1], vec![0]];
+        assert_eq!(
+            schedule(&adj, 2, |_| 1, |n| n),
+            Err(ScheduleError::Cycle(Cycle(vec![0, 1])))
+        );
+    }
+}
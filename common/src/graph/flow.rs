@@ -0,0 +1,429 @@
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::dijkstra::Edge;
+
+/// A graph of capacities. Every edge is stored next to its reverse edge (`i` and `i ^ 1`) so augmenting a path
+/// can push flow back along the reverse edge.
+#[derive(Clone, Debug)]
+pub struct FlowGraph {
+    adj: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    capacity: Vec<i64>,
+}
+
+impl FlowGraph {
+    pub fn new(nodes: usize) -> Self {
+        FlowGraph {
+            adj: vec![vec![]; nodes],
+            to: vec![],
+            capacity: vec![],
+        }
+    }
+
+    /// Build a directed graph from an adjacency list, using the edge cost as the capacity
+    pub fn from_adjacency(adj_list: &[Vec<Edge>]) -> Self {
+        let mut graph = FlowGraph::new(adj_list.len());
+        for (from, edges) in adj_list.iter().enumerate() {
+            for edge in edges {
+                graph.add_edge(from, edge.node, edge.cost as i64);
+            }
+        }
+        graph
+    }
+
+    /// Build an undirected graph where every edge has a capacity of 1 from pairs of labels. Returns the graph
+    /// along with the label of each node.
+    pub fn from_labelled_edges<'a>(
+        edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> (Self, Vec<&'a str>) {
+        let mut labels = vec![];
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut pairs = vec![];
+        for (a, b) in edges {
+            let [a, b] = [a, b].map(|label| {
+                *index.entry(label).or_insert_with(|| {
+                    labels.push(label);
+                    labels.len() - 1
+                })
+            });
+            pairs.push((a, b));
+        }
+
+        let mut graph = FlowGraph::new(labels.len());
+        for (a, b) in pairs {
+            graph.add_undirected_edge(a, b, 1);
+        }
+
+        (graph, labels)
+    }
+
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: i64) {
+        self.add_pair(from, to, capacity, 0);
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, capacity: i64) {
+        self.add_pair(a, b, capacity, capacity);
+    }
+
+    fn add_pair(&mut self, from: usize, to: usize, forward: i64, backward: i64) {
+        self.adj[from].push(self.to.len());
+        self.to.push(to);
+        self.capacity.push(forward);
+
+        self.adj[to].push(self.to.len());
+        self.to.push(from);
+        self.capacity.push(backward);
+    }
+
+    fn from(&self, edge: usize) -> usize {
+        self.to[edge ^ 1]
+    }
+
+    /// Dinic's algorithm for the maximum flow from `source` to `sink`
+    pub fn max_flow(&self, source: usize, sink: usize) -> Flow<'_> {
+        let mut residual = self.capacity.clone();
+        let mut value = 0;
+
+        // nothing has to flow anywhere, and every path would have unlimited capacity
+        if source == sink {
+            return Flow {
+                graph: self,
+                residual,
+                value,
+                source,
+            };
+        }
+
+        while let Some(level) = self.levels(&residual, source, sink) {
+            // the next edge to try from each node, so we never retry a dead end within a phase
+            let mut next = vec![0; self.len()];
+            loop {
+                let pushed = self.augment(&mut residual, &level, &mut next, source, sink);
+                if pushed == 0 {
+                    break;
+                }
+                value += pushed;
+            }
+        }
+
+        Flow {
+            graph: self,
+            residual,
+            value,
+            source,
+        }
+    }
+
+    /// Edmonds–Karp: repeatedly push flow along the shortest augmenting path. Slower than `max_flow` on big graphs
+    /// but simpler to follow.
+    pub fn edmonds_karp(&self, source: usize, sink: usize) -> Flow<'_> {
+        let mut residual = self.capacity.clone();
+        let mut value = 0;
+
+        loop {
+            // bfs for the shortest path, remembering which edge we used to reach each node
+            let mut via = vec![None; self.len()];
+            let mut queue = VecDeque::from([source]);
+            while let Some(v) = queue.pop_front() {
+                if v == sink {
+                    break;
+                }
+
+                for &e in &self.adj[v] {
+                    let w = self.to[e];
+                    if residual[e] > 0 && w != source && via[w].is_none() {
+                        via[w] = Some(e);
+                        queue.push_back(w);
+                    }
+                }
+            }
+
+            if via[sink].is_none() {
+                break;
+            }
+
+            let path: Vec<usize> =
+                std::iter::successors(via[sink], |&e| via[self.from(e)]).collect();
+            let bottleneck = path.iter().map(|&e| residual[e]).min().unwrap();
+            for e in path {
+                residual[e] -= bottleneck;
+                residual[e ^ 1] += bottleneck;
+            }
+            value += bottleneck;
+        }
+
+        Flow {
+            graph: self,
+            residual,
+            value,
+            source,
+        }
+    }
+
+    /// The maximum number of paths from `source` to `sink` that don't share any edges
+    pub fn edge_disjoint_paths(&self, source: usize, sink: usize) -> usize {
+        let mut unit = self.clone();
+        unit.capacity.iter_mut().for_each(|c| *c = (*c).min(1));
+        unit.max_flow(source, sink).value as usize
+    }
+
+    /// Stoer–Wagner global minimum cut of the graph, treating every edge as undirected. Returns the weight of the
+    /// cut and the nodes on one side of it, or `None` if there are fewer than two nodes.
+    pub fn min_cut(&self) -> Option<(i64, Vec<usize>)> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let mut weights: Vec<HashMap<usize, i64>> = vec![HashMap::new(); self.len()];
+        for e in (0..self.to.len()).step_by(2) {
+            let (a, b) = (self.from(e), self.to[e]);
+            if a != b {
+                *weights[a].entry(b).or_default() += self.capacity[e];
+                *weights[b].entry(a).or_default() += self.capacity[e];
+            }
+        }
+
+        // every remaining node stands in for the group of original nodes that have been merged into it
+        let mut groups: Vec<Vec<usize>> = (0..self.len()).map(|v| vec![v]).collect();
+        let mut active: Vec<usize> = (0..self.len()).collect();
+        let mut best: Option<(i64, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // maximum adjacency search: keep adding the node most tightly connected to everything added so far
+            let mut connectivity = vec![0; self.len()];
+            let mut added = vec![false; self.len()];
+            let mut heap: BinaryHeap<(i64, usize)> = active.iter().map(|&v| (0, v)).collect();
+            let mut order = vec![];
+
+            while let Some((weight, v)) = heap.pop() {
+                if added[v] || weight != connectivity[v] {
+                    continue;
+                }
+
+                added[v] = true;
+                order.push(v);
+                for (&u, &w) in &weights[v] {
+                    if !added[u] {
+                        connectivity[u] += w;
+                        heap.push((connectivity[u], u));
+                    }
+                }
+            }
+
+            // the last node added is only attached to the rest through the cut of the phase
+            let &[.., s, t] = order.as_slice() else {
+                unreachable!()
+            };
+            if best.as_ref().is_none_or(|(cut, _)| connectivity[t] < *cut) {
+                best = Some((connectivity[t], groups[t].clone()));
+            }
+
+            // merge t into s
+            let merged = std::mem::take(&mut groups[t]);
+            groups[s].extend(merged);
+            for (u, w) in std::mem::take(&mut weights[t]) {
+                weights[u].remove(&t);
+                if u != s {
+                    *weights[s].entry(u).or_default() += w;
+                    *weights[u].entry(s).or_default() += w;
+                }
+            }
+            active.retain(|&v| v != t);
+        }
+
+        best
+    }
+
+    /// Label every node with its bfs distance from the source through edges with spare capacity
+    fn levels(&self, residual: &[i64], source: usize, sink: usize) -> Option<Vec<usize>> {
+        let mut level = vec![usize::MAX; self.len()];
+        let mut queue = VecDeque::from([source]);
+        level[source] = 0;
+
+        while let Some(v) = queue.pop_front() {
+            for &e in &self.adj[v] {
+                let w = self.to[e];
+                if residual[e] > 0 && level[w] == usize::MAX {
+                    level[w] = level[v] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        (level[sink] != usize::MAX).then_some(level)
+    }
+
+    /// Find a path through the level graph and push as much flow down it as we can. The path is kept on an explicit
+    /// stack of edges rather than by recursing, since it can be as long as the graph.
+    fn augment(
+        &self,
+        residual: &mut [i64],
+        level: &[usize],
+        next: &mut [usize],
+        source: usize,
+        sink: usize,
+    ) -> i64 {
+        let mut path: Vec<usize> = vec![];
+        let mut v = source;
+
+        while v != sink {
+            match self.adj[v].get(next[v]) {
+                Some(&e) if residual[e] > 0 && level[self.to[e]] == level[v] + 1 => {
+                    path.push(e);
+                    v = self.to[e];
+                }
+                Some(_) => next[v] += 1,
+                None => {
+                    // a dead end, so back up and don't come this way again
+                    let Some(e) = path.pop() else {
+                        return 0;
+                    };
+                    v = self.from(e);
+                    next[v] += 1;
+                }
+            }
+        }
+
+        let pushed = path.iter().map(|&e| residual[e]).min().unwrap();
+        for e in path {
+            residual[e] -= pushed;
+            residual[e ^ 1] += pushed;
+        }
+        pushed
+    }
+}
+
+/// The result of a maximum flow computation
+pub struct Flow<'a> {
+    graph: &'a FlowGraph,
+    residual: Vec<i64>,
+    source: usize,
+    pub value: i64,
+}
+
+impl Flow<'_> {
+    /// The nodes that can still be reached from the source, which is the source side of a minimum s-t cut
+    pub fn source_side(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.graph.len()];
+        let mut queue = vec![self.source];
+        seen[self.source] = true;
+
+        while let Some(v) = queue.pop() {
+            for &e in &self.graph.adj[v] {
+                let w = self.graph.to[e];
+                if self.residual[e] > 0 && !seen[w] {
+                    seen[w] = true;
+                    queue.push(w);
+                }
+            }
+        }
+
+        (0..self.graph.len()).filter(|&v| seen[v]).collect()
+    }
+
+    /// The edges that cross a minimum s-t cut, as `(from, to)` pairs. Their capacities add up to the flow value.
+    pub fn min_cut(&self) -> Vec<(usize, usize)> {
+        let mut reachable = vec![false; self.graph.len()];
+        for v in self.source_side() {
+            reachable[v] = true;
+        }
+
+        (0..self.graph.to.len())
+            .filter(|&e| self.graph.capacity[e] > 0)
+            .map(|e| (self.graph.from(e), self.graph.to[e]))
+            .filter(|&(from, to)| reachable[from] && !reachable[to])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FlowGraph;
+    use crate::dijkstra::Edge;
+
+    // the classic CLRS example, with a maximum flow of 23
+    fn network() -> FlowGraph {
+        let edges = [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ];
+
+        let mut adj_list = vec![vec![]; 6];
+        for (from, to, cost) in edges {
+            adj_list[from].push(Edge { node: to, cost });
+        }
+        FlowGraph::from_adjacency(&adj_list)
+    }
+
+    #[test]
+    fn max_flow() {
+        let graph = network();
+        let flow = graph.max_flow(0, 5);
+        assert_eq!(flow.value, 23);
+        assert_eq!(graph.edmonds_karp(0, 5).value, 23);
+
+        assert_eq!(flow.source_side(), vec![0, 1, 2, 4]);
+        let mut cut = flow.min_cut();
+        cut.sort();
+        assert_eq!(cut, vec![(1, 3), (4, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn same_source_and_sink() {
+        let graph = network();
+        assert_eq!(graph.max_flow(2, 2).value, 0);
+        assert_eq!(graph.edmonds_karp(2, 2).value, 0);
+    }
+
+    #[test]
+    fn long_path() {
+        // deep enough to overflow the stack if finding a path recursed once per node
+        let n = 200_000;
+        let mut graph = FlowGraph::new(n);
+        for v in 1..n {
+            graph.add_edge(v - 1, v, 3);
+        }
+        assert_eq!(graph.max_flow(0, n - 1).value, 3);
+    }
+
+    #[test]
+    fn disjoint_paths() {
+        assert_eq!(network().edge_disjoint_paths(0, 5), 2);
+    }
+
+    #[test]
+    fn global_min_cut() {
+        // two triangles joined by a single edge
+        let edges = [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+        ];
+        let (graph, labels) = FlowGraph::from_labelled_edges(edges);
+        let (cut, side) = graph.min_cut().unwrap();
+        assert_eq!(cut, 1);
+
+        let mut side: Vec<&str> = side.into_iter().map(|v| labels[v]).collect();
+        side.sort();
+        assert!(side == ["a", "b", "c"] || side == ["d", "e", "f"]);
+    }
+}
@@ -0,0 +1,76 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Map puzzle labels (like `"ka"` or `"tc"`) to dense indices so they can be used with the graph algorithms, and back
+#[derive(Debug, Clone)]
+pub struct Interner<T> {
+    labels: Vec<T>,
+    index: HashMap<T, usize>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Interner {
+            labels: vec![],
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Interner<T>
+where
+    T: Clone + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of `label`, assigning it the next one if we haven't seen it before
+    pub fn intern(&mut self, label: T) -> usize {
+        *self.index.entry(label).or_insert_with_key(|label| {
+            self.labels.push(label.clone());
+            self.labels.len() - 1
+        })
+    }
+
+    pub fn get(&self, label: &T) -> Option<usize> {
+        self.index.get(label).copied()
+    }
+
+    pub fn label(&self, index: usize) -> &T {
+        &self.labels[index]
+    }
+
+    pub fn labels(&self) -> &[T] {
+        &self.labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Intern both ends of every edge and build an undirected adjacency list out of them. Each edge is listed
+    /// under both of its ends, apart from self-loops which are only listed once.
+    pub fn undirected<I>(edges: I) -> (Self, Vec<Vec<usize>>)
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        let mut interner = Interner::new();
+        let mut adj: Vec<Vec<usize>> = vec![];
+        for (a, b) in edges {
+            let a = interner.intern(a);
+            let b = interner.intern(b);
+            adj.resize(interner.len(), vec![]);
+            adj[a].push(b);
+            // a self-loop only goes in the list once
+            if a != b {
+                adj[b].push(a);
+            }
+        }
+
+        (interner, adj)
+    }
+}
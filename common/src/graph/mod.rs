@@ -0,0 +1,13 @@
+pub mod clique;
+pub mod directed;
+pub mod flow;
+pub mod interner;
+pub mod tsp;
+
+pub use directed::{
+    find_cycle, schedule, strongly_connected_components, topological_sort, Cycle, Schedule,
+    ScheduleError, ScheduledJob,
+};
+pub use flow::{Flow, FlowGraph};
+pub use interner::Interner;
+pub use tsp::{held_karp, Objective, Route};
@@ -0,0 +1,157 @@
+use std::ops::Add;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Route {
+    /// Visit every node once, starting and ending anywhere
+    Open,
+    /// Visit every node once, starting from the given node and ending anywhere
+    OpenFrom(usize),
+    /// Visit every node once and then return to the start
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    Minimize,
+    Maximize,
+}
+
+impl Objective {
+    fn better<C: Ord>(&self, a: C, b: C) -> bool {
+        match self {
+            Objective::Minimize => a < b,
+            Objective::Maximize => a > b,
+        }
+    }
+}
+
+/// Held–Karp dynamic programming over subsets of visited nodes, which is O(2^n n^2) instead of trying all n!
+/// orders. `dist[a][b]` is the cost of going from `a` to `b`, or `None` if you can't (this is the same shape
+/// `dijkstra::all_pairs` returns). Returns the best total cost and the order to visit the nodes in. Closed routes
+/// start from node 0 and include the cost of getting back there, but don't repeat it at the end of the order.
+/// There's no route if `OpenFrom` starts from a node that isn't in `dist`.
+pub fn held_karp<C>(
+    dist: &[Vec<Option<C>>],
+    route: Route,
+    objective: Objective,
+) -> Option<(C, Vec<usize>)>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    let n = dist.len();
+    if n == 0 || matches!(route, Route::OpenFrom(start) if start >= n) {
+        return None;
+    }
+
+    // best[mask * n + v] is the best cost to visit exactly the nodes in mask, finishing at v
+    let full = (1 << n) - 1;
+    let index = |mask: usize, v: usize| mask * n + v;
+    let mut best: Vec<Option<C>> = vec![None; (full + 1) * n];
+    let mut parent = vec![usize::MAX; (full + 1) * n];
+
+    let starts = match route {
+        Route::Open => (0..n).collect(),
+        Route::OpenFrom(start) => vec![start],
+        Route::Closed => vec![0],
+    };
+    for start in starts {
+        best[index(1 << start, start)] = Some(C::default());
+    }
+
+    for mask in 1..=full {
+        for v in (0..n).filter(|v| mask & (1 << v) != 0) {
+            let Some(cost) = best[index(mask, v)] else {
+                continue;
+            };
+
+            for w in (0..n).filter(|w| mask & (1 << w) == 0) {
+                let Some(step) = dist[v][w] else {
+                    continue;
+                };
+
+                let next = index(mask | (1 << w), w);
+                let cost = cost + step;
+                if best[next].is_none_or(|b| objective.better(cost, b)) {
+                    best[next] = Some(cost);
+                    parent[next] = v;
+                }
+            }
+        }
+    }
+
+    let (cost, end) = (0..n)
+        .filter_map(|v| {
+            let cost = best[index(full, v)]?;
+            match route {
+                Route::Closed => Some((cost + dist[v][0]?, v)),
+                _ => Some((cost, v)),
+            }
+        })
+        .reduce(|a, b| if objective.better(b.0, a.0) { b } else { a })?;
+
+    let mut order = vec![];
+    let mut mask = full;
+    let mut v = end;
+    while v != usize::MAX {
+        order.push(v);
+        let previous = parent[index(mask, v)];
+        mask ^= 1 << v;
+        v = previous;
+    }
+    order.reverse();
+
+    Some((cost, order))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{held_karp, Objective, Route};
+
+    // the distances from 2015 day 9: London, Dublin, Belfast
+    fn cities() -> Vec<Vec<Option<u32>>> {
+        vec![
+            vec![Some(0), Some(464), Some(518)],
+            vec![Some(464), Some(0), Some(141)],
+            vec![Some(518), Some(141), Some(0)],
+        ]
+    }
+
+    #[test]
+    fn open_routes() {
+        let (cost, order) = held_karp(&cities(), Route::Open, Objective::Minimize).unwrap();
+        assert_eq!(cost, 605);
+        assert!(order == [0, 1, 2] || order == [2, 1, 0]);
+
+        let (cost, _) = held_karp(&cities(), Route::Open, Objective::Maximize).unwrap();
+        assert_eq!(cost, 982);
+
+        let (cost, order) = held_karp(&cities(), Route::OpenFrom(1), Objective::Minimize).unwrap();
+        assert_eq!(cost, 141 + 518);
+        assert_eq!(order, vec![1, 2, 0]);
+
+        assert_eq!(
+            held_karp(&cities(), Route::OpenFrom(3), Objective::Minimize),
+            None
+        );
+    }
+
+    #[test]
+    fn closed_routes() {
+        let (cost, order) = held_karp(&cities(), Route::Closed, Objective::Minimize).unwrap();
+        assert_eq!(cost, 464 + 141 + 518);
+        assert_eq!(order.len(), 3);
+        assert_eq!(order[0], 0);
+    }
+
+    #[test]
+    fn missing_edges() {
+        let mut dist = cities();
+        dist[0][1] = None;
+        dist[1][0] = None;
+
+        let (cost, order) = held_karp(&dist, Route::Open, Objective::Minimize).unwrap();
+        assert_eq!(cost, 518 + 141);
+        assert!(order == [0, 2, 1] || order == [1, 2, 0]);
+        assert_eq!(held_karp(&dist, Route::Closed, Objective::Minimize), None);
+    }
+}
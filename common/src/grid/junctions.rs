@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use super::{CardinalDirection, Coord, Grid};
+use crate::dijkstra::Edge;
+
+const DIRECTIONS: [CardinalDirection; 4] = [
+    CardinalDirection::North,
+    CardinalDirection::West,
+    CardinalDirection::East,
+    CardinalDirection::South,
+];
+
+/// A maze with all of its corridors squashed down into weighted edges between the junctions
+#[derive(Debug, Clone)]
+pub struct JunctionGraph {
+    pub edges: Vec<Vec<Edge>>,
+    pub coords: Vec<Coord>,
+    pub index: HashMap<Coord, usize>,
+}
+
+impl JunctionGraph {
+    pub fn node(&self, c: Coord) -> Option<usize> {
+        self.index.get(&c).copied()
+    }
+
+    /// The longest path from `from` to `to` that never visits the same junction twice. This is a brute force
+    /// search, so it keeps track of the visited junctions in a bitmask and only supports up to 128 of them.
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<usize> {
+        assert!(
+            self.edges.len() <= 128,
+            "too many junctions for a bitmask: {}",
+            self.edges.len()
+        );
+
+        self.longest_from(from, to, 1 << from)
+    }
+
+    fn longest_from(&self, current: usize, to: usize, visited: u128) -> Option<usize> {
+        if current == to {
+            return Some(0);
+        }
+
+        self.edges[current]
+            .iter()
+            .filter(|edge| visited & (1 << edge.node) == 0)
+            .filter_map(|edge| {
+                let rest = self.longest_from(edge.node, to, visited | (1 << edge.node))?;
+                Some(edge.cost + rest)
+            })
+            .max()
+    }
+}
+
+/// Compress a grid maze into a graph of junctions (squares with more than two ways out) plus the `points` of
+/// interest, with an edge for every corridor between them weighted by its length.
+pub fn contract<T, P>(grid: &Grid<T>, passable: P, points: &[Coord]) -> JunctionGraph
+where
+    P: Fn(&T) -> bool,
+{
+    contract_directed(grid, passable, |_| None, points)
+}
+
+/// Same as `contract`, but squares where `one_way` returns a direction (like slopes) can only be left going that
+/// way, and can't be entered head on from the opposite direction. Corridors with slopes in them become directed
+/// edges.
+pub fn contract_directed<T, P, O>(
+    grid: &Grid<T>,
+    passable: P,
+    one_way: O,
+    points: &[Coord],
+) -> JunctionGraph
+where
+    P: Fn(&T) -> bool,
+    O: Fn(&T) -> Option<CardinalDirection>,
+{
+    let mut coords: Vec<Coord> = points.to_vec();
+    coords.extend(grid.iter().filter_map(|square| {
+        let exits = square
+            .neighbors()
+            .iter()
+            .filter(|n| passable(n.data))
+            .count();
+
+        (passable(square.data) && exits > 2 && !points.contains(&square.coords))
+            .then_some(square.coords)
+    }));
+
+    let index: HashMap<Coord, usize> = coords.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+    let can_move = |from: &T, dir: CardinalDirection, to: &T| {
+        let leaving = one_way(from).is_none_or(|d| d == dir);
+        let entering = one_way(to).is_none_or(|d| d != dir.turn_left().turn_left());
+        passable(to) && leaving && entering
+    };
+
+    let edges = coords
+        .iter()
+        .map(|&start| {
+            let mut edges = vec![];
+
+            // follow every corridor out of this junction until it hits another one
+            for dir in DIRECTIONS {
+                let square = grid.get(start);
+                let Some(first) = square.get_neighbor(dir) else {
+                    continue;
+                };
+
+                if !can_move(square.data, dir, first.data) {
+                    continue;
+                }
+
+                let mut previous = start;
+                let mut current = first;
+                let mut cost = 1;
+                loop {
+                    if let Some(&node) = index.get(&current.coords) {
+                        if node != index[&start] {
+                            edges.push(Edge { node, cost });
+                        }
+                        break;
+                    }
+
+                    // corridors only have one way forward, anything else is a dead end
+                    let mut next = DIRECTIONS.into_iter().filter_map(|dir| {
+                        current
+                            .get_neighbor(dir)
+                            .filter(|n| n.coords != previous)
+                            .filter(|n| can_move(current.data, dir, n.data))
+                    });
+
+                    let (Some(n), None) = (next.next(), next.next()) else {
+                        break;
+                    };
+
+                    previous = current.coords;
+                    current = n;
+                    cost += 1;
+                }
+            }
+
+            edges
+        })
+        .collect();
+
+    JunctionGraph {
+        edges,
+        coords,
+        index,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{contract, contract_directed};
+    use crate::{
+        dijkstra::shortest_path,
+        grid::{CardinalDirection, Grid},
+    };
+
+    fn maze(s: &str) -> Grid<char> {
+        Grid::new(s.lines().map(|l| l.chars().collect()).collect())
+    }
+
+    // two routes from the top left to the junction at (2, 4), of length 6 and 10
+    const MAZE: &str = "\
+.....#
+.###.#
+.#...#
+.#.###
+...>..";
+
+    #[test]
+    fn contraction() {
+        let grid = maze(MAZE);
+        let graph = contract(&grid, |&c| c != '#', &[(0, 0), (5, 4)]);
+
+        // the start, the end, and the junction where the routes split
+        let start = graph.node((0, 0)).unwrap();
+        let end = graph.node((5, 4)).unwrap();
+        assert_eq!(graph.coords.len(), 3);
+
+        assert_eq!(shortest_path(&graph.edges, start, end), Some(9));
+        assert_eq!(graph.longest_path(start, end), Some(13));
+    }
+
+    #[test]
+    fn slopes() {
+        let grid = maze(MAZE);
+        let slope = |&c: &char| (c == '>').then_some(CardinalDirection::East);
+        let graph = contract_directed(&grid, |&c| c != '#', slope, &[(0, 0), (5, 4)]);
+
+        let start = graph.node((0, 0)).unwrap();
+        let end = graph.node((5, 4)).unwrap();
+        assert_eq!(graph.longest_path(start, end), Some(13));
+        assert_eq!(graph.longest_path(end, start), None);
+    }
+}
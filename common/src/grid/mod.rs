@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Display};
 
 pub mod direction;
+pub mod junctions;
 pub mod neighbors;
 pub mod orthogonal;
 pub mod path;
@@ -8,6 +9,7 @@ pub mod position;
 
 use crate::extensions::vecvec::VecVec;
 pub use direction::CardinalDirection;
+pub use junctions::{contract, contract_directed, JunctionGraph};
 pub use neighbors::{AllNeighbors, Direction, HasNeighbors, Neighbors};
 pub use path::Path;
 pub use position::Position;
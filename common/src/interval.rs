@@ -0,0 +1,255 @@
+use std::ops::{Range, RangeInclusive};
+
+use crate::math::Integer;
+
+/// Anything that describes a run of integers, so both `a..b` and `a..=b` can go into an `IntervalSet`
+pub trait IntoInterval<T> {
+    /// The first and last values in the run, or `None` if it's empty
+    fn bounds(self) -> Option<(T, T)>;
+}
+
+impl<T: Integer> IntoInterval<T> for RangeInclusive<T> {
+    fn bounds(self) -> Option<(T, T)> {
+        let (start, end) = self.into_inner();
+        (start <= end).then_some((start, end))
+    }
+}
+
+impl<T: Integer> IntoInterval<T> for Range<T> {
+    fn bounds(self) -> Option<(T, T)> {
+        (self.start < self.end).then(|| (self.start, self.end.checked_sub(T::ONE).unwrap()))
+    }
+}
+
+/// A set of integers stored as runs. The runs are kept sorted, and any that overlap or touch are merged, so each
+/// set only has one representation and lookups can binary search. Runs are stored inclusively so a set can reach
+/// all the way up to `T::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    runs: Vec<(T, T)>,
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { runs: vec![] }
+    }
+
+    /// Add a range, merging it with anything it overlaps or touches
+    pub fn insert(&mut self, range: impl IntoInterval<T>) {
+        let Some((start, end)) = range.bounds() else {
+            return;
+        };
+
+        // runs that end at least one before the new one starts are left alone, as are runs that start at least one
+        // after it ends. everything in between gets swallowed.
+        let low = self
+            .runs
+            .partition_point(|&(_, e)| e.checked_add(T::ONE).is_some_and(|e| e < start));
+        let high = self
+            .runs
+            .partition_point(|&(s, _)| end.checked_add(T::ONE).is_none_or(|end| s <= end));
+
+        let merged = if low < high {
+            (start.min(self.runs[low].0), end.max(self.runs[high - 1].1))
+        } else {
+            (start, end)
+        };
+        self.runs.splice(low..high, [merged]);
+    }
+
+    /// Take a range out, splitting any run that it falls in the middle of
+    pub fn remove(&mut self, range: impl IntoInterval<T>) {
+        let Some((start, end)) = range.bounds() else {
+            return;
+        };
+
+        let low = self.runs.partition_point(|&(_, e)| e < start);
+        let high = self.runs.partition_point(|&(s, _)| s <= end);
+        if low >= high {
+            return;
+        }
+
+        // whatever sticks out past either end survives
+        let (first, last) = (self.runs[low].0, self.runs[high - 1].1);
+        let before = (first < start).then(|| (first, start.checked_sub(T::ONE).unwrap()));
+        let after = (last > end).then(|| (end.checked_add(T::ONE).unwrap(), last));
+        self.runs.splice(low..high, before.into_iter().chain(after));
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        result.extend(other.iter());
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut runs = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.runs.get(i), other.runs.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                runs.push((start, end));
+            }
+
+            // whichever run finishes first can't overlap anything else
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { runs }
+    }
+
+    /// Everything in this set that isn't in `other`
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    /// The runs within `bound` that aren't in the set
+    pub fn gaps(&self, bound: impl IntoInterval<T>) -> IntervalSet<T> {
+        IntervalSet::from_iter([bound]).difference(self)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.run_containing(value).is_some()
+    }
+
+    /// The whole run that `value` is part of, if it's in the set
+    pub fn run_containing(&self, value: T) -> Option<RangeInclusive<T>> {
+        let i = self.runs.partition_point(|&(_, e)| e < value);
+        let &(start, end) = self.runs.get(i)?;
+        (start <= value).then_some(start..=end)
+    }
+
+    /// How many values are in the set. This can be more than `T` can hold (every `u64` is 2⁶⁴ values).
+    pub fn len(&self) -> u128 {
+        self.runs
+            .iter()
+            .map(|&(start, end)| span(start, end) + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.runs.first().map(|&(start, _)| start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.runs.last().map(|&(_, end)| end)
+    }
+
+    /// The runs in order, none of which overlap or touch
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + ExactSizeIterator + '_ {
+        self.runs.iter().map(|&(start, end)| start..=end)
+    }
+}
+
+// the distance from start up to end, which always fits even when the difference doesn't fit in a T
+fn span<T: Integer>(start: T, end: T) -> u128 {
+    match (start.is_negative(), end.is_negative()) {
+        (true, false) => start.magnitude() + end.magnitude(),
+        (true, true) => start.magnitude() - end.magnitude(),
+        _ => end.magnitude() - start.magnitude(),
+    }
+}
+
+impl<T: Integer> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        IntervalSet::from_iter([range])
+    }
+}
+
+impl<T: Integer> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        IntervalSet::from_iter([range])
+    }
+}
+
+impl<T: Integer, R: IntoInterval<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer, R: IntoInterval<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntervalSet;
+
+    #[test]
+    fn coalescing() {
+        let mut set: IntervalSet<i32> = [1..=3, 10..=12, 5..=6].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=3, 5..=6, 10..=12]);
+
+        // touching runs merge, and half-open ranges stop one short
+        set.insert(4..5);
+        set.insert(7..10);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=12]);
+
+        set.remove(4..=6);
+        set.insert(20..20);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=3, 7..=12]);
+        assert_eq!(set.len(), 9);
+
+        assert!(set.contains(8));
+        assert!(!set.contains(5));
+        assert_eq!(set.run_containing(3), Some(1..=3));
+        assert_eq!(set.run_containing(13), None);
+
+        let everything = IntervalSet::from(i64::MIN..=i64::MAX);
+        assert_eq!(everything.len(), 1 << 64);
+        let mut edges = IntervalSet::from(u8::MAX..=u8::MAX);
+        edges.insert(0..=254);
+        assert_eq!(edges.iter().collect::<Vec<_>>(), [0..=255]);
+    }
+
+    #[test]
+    fn operations() {
+        let a: IntervalSet<u32> = [0..=5, 10..=15].into_iter().collect();
+        let b: IntervalSet<u32> = [3..=12, 20..=25].into_iter().collect();
+
+        let union = a.union(&b);
+        assert_eq!(union.iter().collect::<Vec<_>>(), [0..=15, 20..=25]);
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), [3..=5, 10..=12]);
+
+        let difference = a.difference(&b);
+        assert_eq!(difference.iter().collect::<Vec<_>>(), [0..=2, 13..=15]);
+
+        assert_eq!(
+            union.gaps(0..30).iter().collect::<Vec<_>>(),
+            [16..=19, 26..=29]
+        );
+        assert_eq!(union.gaps(0..=u32::MAX).min(), Some(16));
+        assert!(IntervalSet::<u32>::new().intersection(&a).is_empty());
+    }
+}
@@ -1,10 +1,23 @@
+// lets the derive macros refer to `::common` from in here too
+extern crate self as common;
+
+pub mod assign;
+pub mod cycle;
+pub mod digits;
 pub mod dijkstra;
 pub mod extensions;
+pub mod fastparse;
+pub mod geometry;
+pub mod graph;
 pub mod grid;
 pub mod heading;
+pub mod interval;
+pub mod linalg;
 pub mod math;
+pub mod memo;
 pub mod nom;
 pub mod program;
+pub mod search;
 pub mod union_find;
 
 #[macro_export]
@@ -18,6 +31,18 @@ macro_rules! read_input {
     }};
 }
 
+/// The same as `read_input!`, but as raw bytes for `fastparse`
+#[macro_export]
+macro_rules! read_input_bytes {
+    () => {{
+        static INPUT: std::sync::LazyLock<Vec<u8>> = std::sync::LazyLock::new(|| {
+            std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
+                .expect("Failed to read input.txt")
+        });
+        INPUT.as_slice()
+    }};
+}
+
 #[macro_export]
 macro_rules! answer {
     ($func:ident($($args:expr),*)) => {{
@@ -33,29 +58,6 @@ macro_rules! answer {
     }};
 }
 
-pub fn to_number(value: &[u32]) -> u32 {
-    value.iter().fold(0, |acc, x| (acc * 10) + x)
-}
-
-pub fn digits(input: usize) -> Vec<u8> {
-    let mut input = input;
-    let mut v = vec![];
-
-    if input == 0 {
-        return vec![0];
-    }
-
-    while input != 0 {
-        let digit = input % 10;
-        input /= 10;
-
-        v.push(digit as u8);
-    }
-
-    v.reverse();
-    v
-}
-
 pub fn transpose<T: Clone + Copy>(input: &[Vec<T>]) -> Vec<Vec<T>> {
     let width = input[0].len();
     // transpose the nested vec so we can examine each char index
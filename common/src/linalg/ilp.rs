@@ -0,0 +1,307 @@
+use super::{Matrix, Rational};
+
+/// The result of a linear program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LpResult {
+    Optimal { value: Rational, x: Vec<Rational> },
+    Infeasible,
+    Unbounded,
+}
+
+/// A simplex tableau. Each row is a constraint with its right hand side at the end, and the objective row holds
+/// the reduced costs with the negated objective value at the end.
+struct Tableau {
+    rows: Vec<Vec<Rational>>,
+    objective: Vec<Rational>,
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    fn rhs(&self, r: usize) -> &Rational {
+        self.rows[r].last().unwrap()
+    }
+
+    fn pivot(&mut self, r: usize, c: usize) {
+        let scale = self.rows[r][c].recip();
+        self.rows[r].iter_mut().for_each(|x| *x *= &scale);
+
+        let pivot_row = self.rows[r].clone();
+        let others = self
+            .rows
+            .iter_mut()
+            .enumerate()
+            .filter(|&(i, _)| i != r)
+            .map(|(_, row)| row)
+            .chain([&mut self.objective]);
+        for row in others {
+            let factor = row[c].clone();
+            if !factor.is_zero() {
+                for (x, p) in row.iter_mut().zip(&pivot_row) {
+                    *x -= &factor * p;
+                }
+            }
+        }
+
+        self.basis[r] = c;
+    }
+
+    /// Pivot until none of the first `columns` columns can improve the objective. Bland's rule (always the lowest
+    /// index) means this can't go round in circles. Returns false if the objective is unbounded.
+    fn optimise(&mut self, columns: usize) -> bool {
+        while let Some(c) = (0..columns).find(|&c| self.objective[c] < Rational::ZERO) {
+            let leaving = (0..self.rows.len())
+                .filter(|&r| self.rows[r][c] > Rational::ZERO)
+                .min_by(|&a, &b| {
+                    let ratio_a = self.rhs(a) / &self.rows[a][c];
+                    let ratio_b = self.rhs(b) / &self.rows[b][c];
+                    ratio_a
+                        .cmp(&ratio_b)
+                        .then(self.basis[a].cmp(&self.basis[b]))
+                });
+
+            let Some(r) = leaving else {
+                return false;
+            };
+            self.pivot(r, c);
+        }
+
+        true
+    }
+}
+
+/// Minimise `c·x` subject to `Ax = b` and `x >= 0` with the two phase simplex method. All the arithmetic is exact.
+pub fn simplex(a: &Matrix, b: &[Rational], c: &[Rational]) -> LpResult {
+    let (m, n) = (a.rows(), a.cols());
+    assert_eq!(b.len(), m, "b needs one value per row");
+    assert_eq!(c.len(), n, "c needs one value per column");
+
+    // phase one: add an artificial variable to every row and minimise their sum to find a feasible basis
+    let rows: Vec<Vec<Rational>> = (0..m)
+        .map(|r| {
+            // flip rows so the right hand sides are all positive
+            let sign = if b[r].is_negative() {
+                -Rational::ONE
+            } else {
+                Rational::ONE
+            };
+
+            let mut row: Vec<Rational> = a.row(r).iter().map(|x| x * &sign).collect();
+            row.extend((0..m).map(|i| {
+                if i == r {
+                    Rational::ONE
+                } else {
+                    Rational::ZERO
+                }
+            }));
+            row.push(&b[r] * &sign);
+            row
+        })
+        .collect();
+
+    let mut objective = vec![Rational::ZERO; n + m + 1];
+    for row in &rows {
+        for j in (0..n).chain([n + m]) {
+            objective[j] -= &row[j];
+        }
+    }
+
+    let mut tableau = Tableau {
+        rows,
+        objective,
+        basis: (n..n + m).collect(),
+    };
+    tableau.optimise(n + m);
+    if !tableau.objective[n + m].is_zero() {
+        return LpResult::Infeasible;
+    }
+
+    // push any artificial variables that are left (at zero) out of the basis, dropping the rows that are redundant
+    let mut r = 0;
+    while r < tableau.rows.len() {
+        if tableau.basis[r] >= n {
+            match (0..n).find(|&j| !tableau.rows[r][j].is_zero()) {
+                Some(j) => tableau.pivot(r, j),
+                None => {
+                    tableau.rows.remove(r);
+                    tableau.basis.remove(r);
+                    continue;
+                }
+            }
+        }
+        r += 1;
+    }
+
+    // phase two: drop the artificial columns and optimise the real objective
+    for row in &mut tableau.rows {
+        let rhs = row[n + m].clone();
+        row.truncate(n);
+        row.push(rhs);
+    }
+
+    let mut objective: Vec<Rational> = c.iter().cloned().chain([Rational::ZERO]).collect();
+    for (row, &basic) in tableau.rows.iter().zip(&tableau.basis) {
+        let cost = &c[basic];
+        if !cost.is_zero() {
+            for (x, v) in objective.iter_mut().zip(row) {
+                *x -= cost * v;
+            }
+        }
+    }
+    tableau.objective = objective;
+
+    if !tableau.optimise(n) {
+        return LpResult::Unbounded;
+    }
+
+    let mut x = vec![Rational::ZERO; n];
+    for r in 0..tableau.rows.len() {
+        x[tableau.basis[r]] = tableau.rhs(r).clone();
+    }
+
+    LpResult::Optimal {
+        value: -&tableau.objective[n],
+        x,
+    }
+}
+
+/// Minimise `c·x` subject to `Ax = b` over non-negative integers, by branch and bound on the `simplex`
+/// relaxation. Returns `None` if there's no integer solution, and panics if the relaxation is unbounded.
+pub fn minimize_integer(
+    a: &Matrix,
+    b: &[Rational],
+    c: &[Rational],
+) -> Option<(Rational, Vec<i128>)> {
+    let n = a.cols();
+    let mut best: Option<(Rational, Vec<i128>)> = None;
+
+    // every branch tightens the bounds on one variable
+    let mut stack = vec![(vec![0; n], vec![None; n])];
+    while let Some((lower, upper)) = stack.pop() {
+        let LpResult::Optimal { value, x } = relaxation(a, b, c, &lower, &upper) else {
+            continue;
+        };
+
+        if best.as_ref().is_some_and(|(v, _)| value >= *v) {
+            continue;
+        }
+
+        match x.iter().position(|v| !v.is_integer()) {
+            Some(j) => {
+                // either round this variable down or round it up
+                let mut down = upper.clone();
+                down[j] = Some(x[j].floor());
+                let mut up = lower.clone();
+                up[j] = x[j].ceil();
+
+                stack.push((up, upper));
+                stack.push((lower, down));
+            }
+            None => best = Some((value, x.iter().map(|v| v.to_integer().unwrap()).collect())),
+        }
+    }
+
+    best
+}
+
+/// Solve the linear program with `lower <= x <= upper`, by shifting every variable down to its lower bound and
+/// adding a slack variable for every upper bound
+fn relaxation(
+    a: &Matrix,
+    b: &[Rational],
+    c: &[Rational],
+    lower: &[i128],
+    upper: &[Option<i128>],
+) -> LpResult {
+    let n = a.cols();
+    if (0..n).any(|j| upper[j].is_some_and(|u| u < lower[j])) {
+        return LpResult::Infeasible;
+    }
+
+    let bounded: Vec<usize> = (0..n).filter(|&j| upper[j].is_some()).collect();
+    let width = n + bounded.len();
+
+    let mut rows = vec![];
+    let mut rhs = vec![];
+    for r in 0..a.rows() {
+        let mut row = a.row(r).to_vec();
+        row.resize(width, Rational::ZERO);
+        rows.push(row);
+
+        let shifted = (0..n).fold(b[r].clone(), |acc, j| acc - &a[(r, j)] * &lower[j].into());
+        rhs.push(shifted);
+    }
+
+    for (k, &j) in bounded.iter().enumerate() {
+        let mut row = vec![Rational::ZERO; width];
+        row[j] = Rational::ONE;
+        row[n + k] = Rational::ONE;
+        rows.push(row);
+        rhs.push((upper[j].unwrap() - lower[j]).into());
+    }
+
+    let mut costs = c.to_vec();
+    costs.resize(width, Rational::ZERO);
+
+    match simplex(&Matrix::new(rows), &rhs, &costs) {
+        LpResult::Optimal { x, .. } => {
+            let x: Vec<Rational> = (0..n).map(|j| &x[j] + &lower[j].into()).collect();
+            let value = x
+                .iter()
+                .zip(c)
+                .fold(Rational::ZERO, |acc, (x, c)| acc + x * c);
+            LpResult::Optimal { value, x }
+        }
+        LpResult::Infeasible => LpResult::Infeasible,
+        LpResult::Unbounded => panic!("the relaxation is unbounded"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{minimize_integer, simplex, LpResult};
+    use crate::linalg::{Matrix, Rational};
+
+    fn ints(v: &[i64]) -> Vec<Rational> {
+        v.iter().map(|&x| x.into()).collect()
+    }
+
+    #[test]
+    fn linear_program() {
+        // maximise x + y with 2x + y <= 4 and x + 2y <= 4, written as equalities with slack variables
+        let a = Matrix::from_integers(&[vec![2i64, 1, 1, 0], vec![1, 2, 0, 1]]);
+        let b = ints(&[4, 4]);
+        let LpResult::Optimal { value, x } = simplex(&a, &b, &ints(&[-1, -1, 0, 0])) else {
+            panic!("expected a solution");
+        };
+        assert_eq!(value, Rational::new(-8, 3));
+        assert_eq!(
+            x,
+            vec![Rational::new(4, 3), Rational::new(4, 3), 0.into(), 0.into()]
+        );
+
+        // dropping the second constraint lets y grow forever
+        let a = Matrix::from_integers(&[vec![2i64, -1, 1]]);
+        assert_eq!(
+            simplex(&a, &ints(&[4]), &ints(&[0, -1, 0])),
+            LpResult::Unbounded
+        );
+
+        let a = Matrix::from_integers(&[vec![1i64, 1]]);
+        assert_eq!(
+            simplex(&a, &ints(&[-1]), &ints(&[1, 1])),
+            LpResult::Infeasible
+        );
+    }
+
+    #[test]
+    fn integer_program() {
+        // make 7 out of 2s and 3s with as few pieces as possible
+        let a = Matrix::from_integers(&[vec![2i64, 3]]);
+        let result = minimize_integer(&a, &ints(&[7]), &ints(&[1, 1]));
+        assert_eq!(result, Some((3.into(), vec![2, 1])));
+
+        // but there's no way to make an odd number out of 2s and 4s
+        let a = Matrix::from_integers(&[vec![2i64, 4]]);
+        assert_eq!(minimize_integer(&a, &ints(&[7]), &ints(&[1, 1])), None);
+    }
+}
@@ -0,0 +1,178 @@
+use std::ops::Index;
+
+use super::Rational;
+
+/// A dense matrix of exact fractions, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: Vec<Vec<Rational>>,
+    cols: usize,
+}
+
+impl Matrix {
+    pub fn new(rows: Vec<Vec<Rational>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "every row needs the same number of columns"
+        );
+
+        Matrix { rows, cols }
+    }
+
+    pub fn from_integers<T>(rows: &[Vec<T>]) -> Self
+    where
+        T: Copy + Into<Rational>,
+    {
+        Matrix::new(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| x.into()).collect())
+                .collect(),
+        )
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, r: usize) -> &[Rational] {
+        &self.rows[r]
+    }
+
+    /// Gauss–Jordan elimination into reduced row echelon form. Returns the reduced matrix along with the column
+    /// of the leading one in each non-zero row.
+    pub fn rref(&self) -> (Matrix, Vec<usize>) {
+        let mut rows = self.rows.clone();
+        let mut pivots = vec![];
+
+        for col in 0..self.cols {
+            let r = pivots.len();
+            let Some(pivot) = (r..rows.len()).find(|&i| !rows[i][col].is_zero()) else {
+                continue;
+            };
+            rows.swap(r, pivot);
+
+            let scale = rows[r][col].recip();
+            rows[r].iter_mut().for_each(|x| *x *= &scale);
+
+            let pivot_row = rows[r].clone();
+            for (i, row) in rows.iter_mut().enumerate() {
+                let factor = row[col].clone();
+                if i == r || factor.is_zero() {
+                    continue;
+                }
+                for (x, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *x -= &factor * p;
+                }
+            }
+
+            pivots.push(col);
+            if pivots.len() == rows.len() {
+                break;
+            }
+        }
+
+        (
+            Matrix {
+                rows,
+                cols: self.cols,
+            },
+            pivots,
+        )
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// A basis for the vectors `x` where `Ax = 0`, with one vector for every free column
+    pub fn nullspace(&self) -> Vec<Vec<Rational>> {
+        let (reduced, pivots) = self.rref();
+
+        (0..self.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                // set this free variable to one and the others to zero, then the pivots follow
+                let mut v = vec![Rational::ZERO; self.cols];
+                v[free] = Rational::ONE;
+                for (r, &p) in pivots.iter().enumerate() {
+                    v[p] = -&reduced[(r, free)];
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// Some `x` where `Ax = b`, with every free variable set to zero, or `None` if there's no solution. Every
+    /// other solution is this one plus a combination of the `nullspace`.
+    pub fn solve(&self, b: &[Rational]) -> Option<Vec<Rational>> {
+        assert_eq!(b.len(), self.rows(), "b needs one value per row");
+
+        let augmented = Matrix::new(
+            self.rows
+                .iter()
+                .zip(b)
+                .map(|(row, b)| row.iter().chain([b]).cloned().collect())
+                .collect(),
+        );
+        let (reduced, pivots) = augmented.rref();
+
+        // a pivot in the last column means a row that says 0 = 1
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        let mut x = vec![Rational::ZERO; self.cols];
+        for (r, &p) in pivots.iter().enumerate() {
+            x[p] = reduced[(r, self.cols)].clone();
+        }
+        Some(x)
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+
+    fn index(&self, (r, c): (usize, usize)) -> &Self::Output {
+        &self.rows[r][c]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Matrix;
+    use crate::linalg::Rational;
+
+    fn ints(v: &[i64]) -> Vec<Rational> {
+        v.iter().map(|&x| x.into()).collect()
+    }
+
+    #[test]
+    fn solving() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let a = Matrix::from_integers(&[vec![1i64, 1, 1], vec![0, 2, 5], vec![2, 5, -1]]);
+        assert_eq!(a.rank(), 3);
+        assert_eq!(a.solve(&ints(&[6, -4, 27])), Some(ints(&[5, 3, -2])));
+        assert!(a.nullspace().is_empty());
+    }
+
+    #[test]
+    fn underdetermined() {
+        // the second row is twice the first
+        let a = Matrix::from_integers(&[vec![1i64, 2, 3], vec![2, 4, 6]]);
+        assert_eq!(a.rank(), 1);
+        assert_eq!(a.solve(&ints(&[1, 3])), None);
+        assert_eq!(a.solve(&ints(&[1, 2])), Some(ints(&[1, 0, 0])));
+
+        let nullspace = a.nullspace();
+        assert_eq!(nullspace, vec![ints(&[-2, 1, 0]), ints(&[-3, 0, 1])]);
+        for v in nullspace {
+            let dot = (0..3).fold(Rational::ZERO, |acc, j| acc + &a[(0, j)] * &v[j]);
+            assert!(dot.is_zero());
+        }
+    }
+}
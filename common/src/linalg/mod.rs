@@ -0,0 +1,7 @@
+pub mod ilp;
+pub mod matrix;
+pub mod rational;
+
+pub use ilp::{minimize_integer, simplex, LpResult};
+pub use matrix::Matrix;
+pub use rational::Rational;
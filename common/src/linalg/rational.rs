@@ -0,0 +1,329 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator. Small fractions are plain `i128`s
+/// so they're quick, and anything that would overflow moves over to a `BigInt` instead of giving the wrong answer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational(Repr);
+
+// a fraction is only ever big if it doesn't fit in small, so there's exactly one way to store every value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    Small(i128, i128),
+    Big(BigInt, BigInt),
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational(Repr::Small(0, 1));
+    pub const ONE: Rational = Rational(Repr::Small(1, 1));
+
+    pub fn new(numer: i128, denom: i128) -> Self {
+        Rational::from_big(numer.into(), denom.into())
+    }
+
+    /// Reduce a fraction to lowest terms, shrinking it back down to `i128`s if it fits
+    fn from_big(numer: BigInt, denom: BigInt) -> Self {
+        assert!(!denom.is_zero(), "rational with a zero denominator");
+
+        let g = numer.gcd(&denom);
+        let (mut numer, mut denom) = (numer / &g, denom / g);
+        if denom.is_negative() {
+            (numer, denom) = (-numer, -denom);
+        }
+
+        match (numer.to_i128(), denom.to_i128()) {
+            (Some(n), Some(d)) => Rational(Repr::Small(n, d)),
+            _ => Rational(Repr::Big(numer, denom)),
+        }
+    }
+
+    fn big(&self) -> (BigInt, BigInt) {
+        match &self.0 {
+            Repr::Small(n, d) => ((*n).into(), (*d).into()),
+            Repr::Big(n, d) => (n.clone(), d.clone()),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        matches!(self.0, Repr::Small(0, _))
+    }
+
+    pub fn is_negative(&self) -> bool {
+        match &self.0 {
+            Repr::Small(n, _) => *n < 0,
+            Repr::Big(n, _) => n.is_negative(),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match &self.0 {
+            Repr::Small(_, d) => *d == 1,
+            Repr::Big(_, d) => d.is_one(),
+        }
+    }
+
+    /// The value as an integer, if it is one and it fits
+    pub fn to_integer(&self) -> Option<i128> {
+        match self.0 {
+            Repr::Small(n, 1) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The biggest integer that's no bigger than this, panicking if it doesn't fit in an `i128`
+    pub fn floor(&self) -> i128 {
+        let (n, d) = self.big();
+        n.div_floor(&d)
+            .to_i128()
+            .expect("rational too big to round")
+    }
+
+    /// The smallest integer that's no smaller than this, panicking if it doesn't fit in an `i128`
+    pub fn ceil(&self) -> i128 {
+        let (n, d) = self.big();
+        n.div_ceil(&d).to_i128().expect("rational too big to round")
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.is_negative() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    pub fn recip(&self) -> Self {
+        let (n, d) = self.big();
+        Rational::from_big(d, n)
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::ZERO
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational(Repr::Small(value, 1))
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational::from(value as i128)
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(value: i32) -> Self {
+        Rational::from(value as i128)
+    }
+}
+
+impl Add<&Rational> for &Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: &Rational) -> Self::Output {
+        if let (Repr::Small(a, b), Repr::Small(c, d)) = (&self.0, &rhs.0) {
+            // scale by the lcm rather than the product of the denominators to keep the numbers small
+            let g = gcd(*b, *d);
+            let numer = a
+                .checked_mul(d / g)
+                .zip(c.checked_mul(b / g))
+                .and_then(|(x, y)| x.checked_add(y));
+            let denom = (b / g).checked_mul(*d);
+
+            if let (Some(numer), Some(denom)) = (numer, denom) {
+                let g = gcd(numer, denom);
+                return Rational(Repr::Small(numer / g, denom / g));
+            }
+        }
+
+        let ((a, b), (c, d)) = (self.big(), rhs.big());
+        Rational::from_big(a * &d + c * &b, b * d)
+    }
+}
+
+impl Sub<&Rational> for &Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: &Rational) -> Self::Output {
+        self + &-rhs
+    }
+}
+
+impl Mul<&Rational> for &Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: &Rational) -> Self::Output {
+        if let (Repr::Small(a, b), Repr::Small(c, d)) = (&self.0, &rhs.0) {
+            // cancel across before multiplying, then the result is already in lowest terms
+            let g1 = gcd(*a, *d);
+            let g2 = gcd(*c, *b);
+            let numer = (a / g1).checked_mul(c / g2);
+            let denom = (b / g2).checked_mul(d / g1);
+            if let (Some(numer), Some(denom)) = (numer, denom) {
+                return Rational(Repr::Small(numer, denom));
+            }
+        }
+
+        let ((a, b), (c, d)) = (self.big(), rhs.big());
+        Rational::from_big(a * c, b * d)
+    }
+}
+
+impl Div<&Rational> for &Rational {
+    type Output = Rational;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: &Rational) -> Self::Output {
+        self * &rhs.recip()
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        match self.0 {
+            Repr::Small(n, d) if n != i128::MIN => Rational(Repr::Small(-n, d)),
+            _ => {
+                let (n, d) = self.big();
+                Rational::from_big(-n, d)
+            }
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+// the owned versions just borrow, and the assigning versions take either
+macro_rules! forward_ops {
+    ($($op:ident $method:ident $assign:ident $assign_method:ident),*) => {$(
+        impl $op for Rational {
+            type Output = Rational;
+
+            fn $method(self, rhs: Rational) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $op<&Rational> for Rational {
+            type Output = Rational;
+
+            fn $method(self, rhs: &Rational) -> Self::Output {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $assign for Rational {
+            fn $assign_method(&mut self, rhs: Rational) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+
+        impl $assign<&Rational> for Rational {
+            fn $assign_method(&mut self, rhs: &Rational) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+    )*};
+}
+
+forward_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign
+);
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let (Repr::Small(a, b), Repr::Small(c, d)) = (&self.0, &other.0) {
+            if b == d {
+                return a.cmp(c);
+            }
+            if let (Some(left), Some(right)) = (a.checked_mul(*d), c.checked_mul(*b)) {
+                return left.cmp(&right);
+            }
+        }
+
+        let ((a, b), (c, d)) = (self.big(), other.big());
+        (a * d).cmp(&(c * b))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (n, d) = self.big();
+        if d.is_one() {
+            write!(f, "{n}")
+        } else {
+            write!(f, "{n}/{d}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rational;
+
+    #[test]
+    fn arithmetic() {
+        let half = Rational::new(2, 4);
+        let third = Rational::new(-1, -3);
+        assert_eq!(half, Rational::new(1, 2));
+        assert_eq!(half.clone() + third.clone(), Rational::new(5, 6));
+        assert_eq!(third.clone() - half.clone(), Rational::new(-1, 6));
+        assert_eq!(half.clone() * third.clone(), Rational::new(1, 6));
+        assert_eq!(half.clone() / third.clone(), Rational::new(3, 2));
+        assert!(third < half);
+
+        let x = Rational::new(-7, 2);
+        assert_eq!((x.floor(), x.ceil()), (-4, -3));
+        assert_eq!(x.to_string(), "-7/2");
+        assert_eq!(Rational::from(3i64).to_integer(), Some(3));
+    }
+
+    #[test]
+    fn overflow() {
+        // squaring this doesn't fit in an i128, but dividing it back down does
+        let big = Rational::from(i128::MAX);
+        let square = big.clone() * big.clone();
+        assert_eq!(square.to_integer(), None);
+        assert_eq!(
+            square.to_string(),
+            "28948022309329048855892746252171976962977213799489202546401021394546514198529"
+        );
+        assert!(square > big);
+        assert_eq!(square / big.clone(), big);
+    }
+}
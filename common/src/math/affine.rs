@@ -0,0 +1,122 @@
+use super::{add_mod, inverse, mod_mul, modulus, Integer};
+
+/// The map `x -> a * x + b (mod m)`. These are closed under composition, so a long list of steps (like the moves
+/// in a card shuffle) collapses into one, and repeating it `k` times only takes `log k` compositions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Affine<T> {
+    pub a: T,
+    pub b: T,
+    pub m: T,
+}
+
+impl<T: Integer> Affine<T> {
+    pub fn new(a: T, b: T, m: T) -> Self {
+        Affine {
+            a: reduce(a, m),
+            b: reduce(b, m),
+            m,
+        }
+    }
+
+    /// The map that leaves everything where it is
+    pub fn identity(m: T) -> Self {
+        Affine::new(T::ONE, T::ZERO, m)
+    }
+
+    pub fn apply(&self, x: T) -> T {
+        add(mod_mul(self.a, x, self.m), self.b, self.m)
+    }
+
+    /// This map followed by `next`
+    pub fn then(&self, next: &Affine<T>) -> Affine<T> {
+        assert_eq!(self.m, next.m, "can't compose maps with different moduli");
+
+        Affine {
+            a: mod_mul(next.a, self.a, self.m),
+            b: next.apply(self.b),
+            m: self.m,
+        }
+    }
+
+    /// The map that undoes this one, if `a` is invertible
+    pub fn inverse(&self) -> Option<Affine<T>> {
+        // y = ax + b, so x = a⁻¹y - a⁻¹b
+        let a = inverse(self.a, self.m)?;
+        let b = mod_mul(a, self.b, self.m);
+        let b = T::from_u128(modulus(self.m) - b.magnitude()).unwrap();
+        Some(Affine::new(a, b, self.m))
+    }
+
+    /// This map applied `k` times in a row. Negative `k` repeats the inverse, so it's `None` if there isn't one.
+    pub fn pow<E: Integer>(&self, k: E) -> Option<Affine<T>> {
+        let mut base = if k.is_negative() {
+            self.inverse()?
+        } else {
+            *self
+        };
+
+        let mut k = k.magnitude();
+        let mut result = Affine::identity(self.m);
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.then(&base);
+            }
+            base = base.then(&base);
+            k >>= 1;
+        }
+
+        Some(result)
+    }
+}
+
+fn reduce<T: Integer>(x: T, m: T) -> T {
+    T::from_u128(x.rem_euclid_u128(modulus(m))).unwrap()
+}
+
+fn add<T: Integer>(x: T, y: T, m: T) -> T {
+    let m = modulus(m);
+    let sum = add_mod(x.rem_euclid_u128(m), y.rem_euclid_u128(m), m);
+    T::from_u128(sum).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Affine;
+
+    #[test]
+    fn composition() {
+        let f = Affine::new(3, -1, 10);
+        let g = Affine::new(7, 4, 10);
+        assert_eq!(f, Affine::new(3, 9, 10));
+        assert_eq!(f.apply(4), 1);
+
+        for x in 0..10 {
+            assert_eq!(f.then(&g).apply(x), g.apply(f.apply(x)));
+            assert_eq!(f.inverse().unwrap().apply(f.apply(x)), x);
+        }
+
+        // 2 has no inverse mod 10
+        assert_eq!(Affine::new(2, 1, 10).inverse(), None);
+    }
+
+    #[test]
+    fn repetition() {
+        let f = Affine::new(3u64, 5, 1_000_003);
+        let mut x = 42;
+        for _ in 0..1000 {
+            x = f.apply(x);
+        }
+        assert_eq!(f.pow(1000).unwrap().apply(42), x);
+        assert_eq!(f.pow(-1000).unwrap().apply(x), 42);
+        assert_eq!(f.pow(0), Some(Affine::identity(1_000_003)));
+
+        // far too many to do one at a time, and the modulus is too big to multiply in a u64
+        let m = u64::MAX - 58;
+        let f = Affine::new(m - 1, 1, m);
+        let k = 101_741_582_076_661u64;
+        assert_eq!(
+            f.pow(k).unwrap().then(&f.pow(-(k as i64)).unwrap()),
+            Affine::identity(m)
+        );
+    }
+}
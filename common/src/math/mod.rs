@@ -0,0 +1,386 @@
+pub mod affine;
+pub mod primes;
+pub mod sequence;
+
+pub use affine::Affine;
+pub use primes::{divisors, factorize, is_prime, sigma, tau};
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+/// The primitive integer types, so the number theory helpers work for all of them. Anything modular is done in
+/// `u128` under the hood, so it can't overflow no matter which type the values come in as.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn is_negative(self) -> bool;
+
+    /// The absolute value, which always fits in a `u128`
+    fn magnitude(self) -> u128;
+
+    /// The value reduced into `0..modulus`, wrapping negative values around from the top
+    fn rem_euclid_u128(self, modulus: u128) -> u128;
+
+    fn from_u128(value: u128) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            #[allow(unused_comparisons)]
+            fn is_negative(self) -> bool {
+                self < 0
+            }
+
+            fn magnitude(self) -> u128 {
+                self.unsigned_abs() as u128
+            }
+
+            fn rem_euclid_u128(self, modulus: u128) -> u128 {
+                let r = self.magnitude() % modulus;
+                if self.is_negative() && r != 0 {
+                    modulus - r
+                } else {
+                    r
+                }
+            }
+
+            fn from_u128(value: u128) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+        }
+    )*};
+}
+
+/// The integer types that can go below zero, for anything that needs negative answers
+pub trait Signed: Integer {}
+
+macro_rules! signed {
+    ($($t:ty)*) => {$(
+        impl Signed for $t {}
+    )*};
+}
+
+// unsigned_abs doesn't exist for unsigned types, so give them one that does nothing
+trait UnsignedAbs {
+    fn unsigned_abs(self) -> Self;
+}
+
+macro_rules! unsigned_abs {
+    ($($t:ty)*) => {$(
+        impl UnsignedAbs for $t {
+            fn unsigned_abs(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+signed!(i8 i16 i32 i64 i128 isize);
+unsigned_abs!(u8 u16 u32 u64 u128 usize);
+
+/// The greatest common divisor, which is always positive (or zero if both are zero). Panics if the answer doesn't
+/// fit, which can only happen for something like `gcd(i64::MIN, 0)`.
+pub fn gcd<T: Integer>(x: T, y: T) -> T {
+    let (mut x, mut y) = (x.magnitude(), y.magnitude());
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    T::from_u128(x).expect("gcd overflow")
+}
+
+/// The least common multiple, or `None` if it doesn't fit
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    // divide first so only the answer itself can overflow. the gcd is worked out on the magnitudes, since it might
+    // not fit back into a T (like it doesn't for gcd(i64::MIN, i64::MIN))
+    let (a, b) = (a.magnitude(), b.magnitude());
+    let m = (a / gcd(a, b)).checked_mul(b)?;
+    T::from_u128(m)
+}
+
+/// The greatest common divisor of everything, or zero if there's nothing
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The least common multiple of everything (like a set of cycle lengths), or `None` if it doesn't fit
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().try_fold(T::ONE, |acc, x| lcm(acc, x))
+}
+
+// pretty much ripped directly from https://github.com/rust-lang/rust/pull/88582/files#diff-dd440fe33121a785308d5cde98a1ab79b0b285d27bb29eaa9800e180870e16a6R1809
+pub fn div_ceil<T: Integer>(lhs: T, rhs: T) -> T {
+    let d = lhs / rhs;
+    let r = lhs % rhs;
+    if (r > T::ZERO && rhs > T::ZERO) || (r < T::ZERO && rhs < T::ZERO) {
+        d + T::ONE
+    } else {
+        d
+    }
+}
+
+// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+/// Returns `(g, x, y)` where `g = gcd(a, b)` and `ax + by = g`, or `None` if any of them don't fit. Only for signed
+/// types, since one of the coefficients is usually negative.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r.checked_sub(q.checked_mul(r)?)?);
+        (old_s, s) = (s, old_s.checked_sub(q.checked_mul(s)?)?);
+        (old_t, t) = (t, old_t.checked_sub(q.checked_mul(t)?)?);
+    }
+
+    // keep the gcd positive
+    if old_r.is_negative() {
+        let negate = |x: T| T::ZERO.checked_sub(x);
+        return Some((negate(old_r)?, negate(old_s)?, negate(old_t)?));
+    }
+
+    Some((old_r, old_s, old_t))
+}
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn mul_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if m <= 1 << 64 {
+        return a * b % m;
+    }
+
+    // the product won't fit, so double and add instead
+    let (mut a, mut b) = (a % m, b);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+fn modulus<T: Integer>(m: T) -> u128 {
+    assert!(m > T::ZERO, "modulus must be positive, not {m:?}");
+    m.magnitude()
+}
+
+/// `a * b` in `0..m`, without overflowing along the way
+pub fn mod_mul<T: Integer>(a: T, b: T, m: T) -> T {
+    let m = modulus(m);
+    let result = mul_mod_u128(a.rem_euclid_u128(m), b.rem_euclid_u128(m), m);
+    T::from_u128(result).unwrap()
+}
+
+/// `base ^ exp` in `0..m`. Negative exponents use the inverse of the base, so they're `None` if there isn't one.
+pub fn mod_pow<T: Integer, E: Integer>(base: T, exp: E, m: T) -> Option<T> {
+    let base = if exp.is_negative() {
+        inverse(base, m)?
+    } else {
+        base
+    };
+
+    let m = modulus(m);
+    let mut base = base.rem_euclid_u128(m);
+    let mut exp = exp.magnitude();
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u128(result, base, m);
+        }
+        exp >>= 1;
+        base = mul_mod_u128(base, base, m);
+    }
+
+    T::from_u128(result)
+}
+
+// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm#Computing_multiplicative_inverses_in_modular_structures
+/// The `y` in `0..n` where `x * y = 1 (mod n)`, if there is one
+pub fn inverse<T: Integer>(x: T, n: T) -> Option<T> {
+    let n = modulus(n);
+
+    // the extended euclidean algorithm, but only tracking the coefficient of x, and keeping it in 0..n
+    let (mut old_r, mut r) = (n, x.rem_euclid_u128(n));
+    let (mut old_t, mut t) = (0, 1 % n);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_t, t) = (t, add_mod(old_t, n - mul_mod_u128(q, t, n), n) % n);
+    }
+
+    (old_r == 1).then(|| T::from_u128(old_t).unwrap())
+}
+
+/// Why a set of congruences couldn't be combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// The congruence at this index contradicts the ones before it
+    Inconsistent(usize),
+    /// The combined modulus (or residue) doesn't fit
+    Overflow,
+}
+
+// and finally https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+/// Combine congruences `x = residue (mod modulus)` into the single `(residue, modulus)` that satisfies all of them.
+/// The moduli don't have to be coprime, so congruences are merged one at a time, checking they agree wherever
+/// their moduli overlap. Nothing is ever multiplied out past the final modulus, and all of it happens in `u128`.
+pub fn chinese_remainder<T: Integer>(
+    congruences: impl IntoIterator<Item = (T, T)>,
+) -> Result<(T, T), CrtError> {
+    let (mut r, mut m) = (0u128, 1u128);
+
+    for (i, (residue, n)) in congruences.into_iter().enumerate() {
+        let n = modulus(n);
+        let a = residue.rem_euclid_u128(n);
+
+        // the two only have to agree modulo what their moduli have in common
+        let g = gcd(m, n);
+        if a % g != r % g {
+            return Err(CrtError::Inconsistent(i));
+        }
+
+        let lcm = (m / g).checked_mul(n).ok_or(CrtError::Overflow)?;
+
+        // step r along by multiples of m until it also lands on a (mod n)
+        let diff = add_mod(a, n - r % n, n) % n;
+        let step = inverse(m / g, n / g).unwrap();
+        let k = mul_mod_u128(diff / g, step, n / g);
+        (r, m) = (r + m * k, lcm);
+    }
+
+    let residue = T::from_u128(r).ok_or(CrtError::Overflow)?;
+    let modulus = T::from_u128(m).ok_or(CrtError::Overflow)?;
+    Ok((residue, modulus))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        chinese_remainder, extended_gcd, gcd, gcd_all, inverse, lcm, lcm_all, mod_mul, mod_pow,
+        CrtError,
+    };
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4i32, 6), Some(12));
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+
+        // the product overflows but the answer doesn't
+        let big = 1u64 << 63;
+        assert_eq!(lcm(big, big), Some(big));
+        assert_eq!(lcm(big, 3), None);
+
+        // the gcd doesn't fit either, but that shouldn't stop lcm from saying so
+        assert_eq!(lcm(i64::MIN, i64::MIN), None);
+        assert_eq!(lcm(i64::MIN, 2), None);
+        assert_eq!(lcm_all([3, i64::MIN]), None);
+        assert_eq!(lcm(i64::MIN + 1, 1), Some(i64::MAX));
+    }
+
+    #[test]
+    fn bezout() {
+        assert_eq!(extended_gcd(240, 46), Some((2, -9, 47)));
+        assert_eq!(extended_gcd(-240i64, 46), Some((2, 9, 47)));
+        assert_eq!(extended_gcd(0i8, 0), Some((0, 1, 0)));
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(inverse(3, 11), Some(4));
+        assert_eq!(inverse(-3, 11), Some(7));
+        assert_eq!(inverse(6, 9), None);
+
+        assert_eq!(mod_pow(4, 13, 497), Some(445));
+        assert_eq!(mod_pow(3, -1, 11), Some(4));
+        assert_eq!(mod_pow(6, -1, 9), None);
+
+        // both of these overflow if the multiplication is done in the original type
+        let m = u64::MAX - 58;
+        assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+        let m = u128::MAX - 158;
+        assert_eq!(mod_pow(m - 1, 3u32, m), Some(m - 1));
+        assert_eq!(inverse(m - 1, m), Some(m - 1));
+    }
+
+    #[test]
+    fn congruences() {
+        // the classic from Sunzi: 2 mod 3, 3 mod 5, 2 mod 7
+        assert_eq!(chinese_remainder([(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+
+        // 6 and 4 overlap, so they only need to agree mod 2
+        assert_eq!(chinese_remainder([(-1i64, 6), (3, 4)]), Ok((11, 12)));
+        assert_eq!(
+            chinese_remainder([(1, 6), (2, 4)]),
+            Err(CrtError::Inconsistent(1))
+        );
+
+        assert_eq!(chinese_remainder::<u8>([]), Ok((0, 1)));
+        assert_eq!(
+            chinese_remainder([(1u8, 251), (1, 241)]),
+            Err(CrtError::Overflow)
+        );
+
+        // the product of these is way past a u64, but the moduli share most of their factors
+        let big = 1u64 << 62;
+        assert_eq!(
+            chinese_remainder([(5, big), (5, big / 2), (5, big * 2)]),
+            Ok((5, big * 2))
+        );
+        assert_eq!(
+            chinese_remainder([(5, big), (5, big / 2), (0, 5)]),
+            Err(CrtError::Overflow)
+        );
+    }
+}
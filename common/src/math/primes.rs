@@ -0,0 +1,241 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use super::{gcd, mod_mul, mod_pow};
+
+// how many numbers to sieve at once, so big ranges don't need a huge buffer
+const SEGMENT: u64 = 1 << 16;
+
+// checking these bases is enough to be certain for anything that fits in a u64
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Every prime up to and including `limit`
+pub fn primes_up_to(limit: u64) -> Vec<u64> {
+    primes_in(2..=limit)
+}
+
+/// Every prime in the range, using a segmented sieve of Eratosthenes. Only the primes up to the square root of the
+/// end are kept around, so this works for ranges a long way from zero.
+pub fn primes_in(range: RangeInclusive<u64>) -> Vec<u64> {
+    let (low, high) = ((*range.start()).max(2), *range.end());
+    if low > high {
+        return vec![];
+    }
+
+    let base = primes_up_to(high.isqrt());
+    let mut primes = vec![];
+
+    let mut start = low;
+    loop {
+        let end = high.min(start.saturating_add(SEGMENT - 1));
+        let mut composite = vec![false; (end - start + 1) as usize];
+
+        for &p in base.iter().take_while(|&&p| p * p <= end) {
+            // anything smaller than p² has a smaller factor, so it's already been crossed off
+            let first = (p * p).max(start.div_ceil(p).saturating_mul(p));
+            for multiple in (first..=end).step_by(p as usize) {
+                composite[(multiple - start) as usize] = true;
+            }
+        }
+
+        primes.extend((start..=end).filter(|n| !composite[(n - start) as usize]));
+
+        if end == high {
+            return primes;
+        }
+        start = end + 1;
+    }
+}
+
+// https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test
+/// A deterministic Miller–Rabin test, which is exact for every `u64`
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = WITNESSES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    WITNESSES.iter().all(|&a| {
+        let mut x = mod_pow(a, d, n).unwrap();
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+
+        (1..s).any(|_| {
+            x = mod_mul(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+/// The prime factors of `n` along with their powers, smallest first. Small factors are divided out directly, and
+/// anything left over is split with Pollard's rho.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "can't factorize zero");
+
+    let mut factors = BTreeMap::new();
+    let mut n = n;
+    for p in WITNESSES {
+        while n.is_multiple_of(p) {
+            *factors.entry(p).or_insert(0) += 1;
+            n /= p;
+        }
+    }
+
+    let mut remaining = vec![n];
+    while let Some(n) = remaining.pop() {
+        if n == 1 {
+            continue;
+        }
+
+        if is_prime(n) {
+            *factors.entry(n).or_insert(0) += 1;
+        } else {
+            let d = pollard_rho(n);
+            remaining.extend([d, n / d]);
+        }
+    }
+
+    factors.into_iter().collect()
+}
+
+// https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm
+/// Some factor of `n` other than 1 or `n`, which has to be composite
+fn pollard_rho(n: u64) -> u64 {
+    (1..)
+        .find_map(|c| {
+            let f = |x| ((mod_mul(x, x, n) as u128 + c) % n as u128) as u64;
+            let (mut x, mut y, mut d) = (2, 2, 1);
+            while d == 1 {
+                x = f(x);
+                y = f(f(y));
+                d = gcd(x.abs_diff(y), n);
+            }
+
+            // landing on n means the sequence cycled without finding anything, so try another constant
+            (d != n).then_some(d)
+        })
+        .unwrap()
+}
+
+/// Every divisor of `n`, in order
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for (p, k) in factorize(n) {
+        let smaller = divisors.clone();
+        let mut power = 1;
+        for _ in 0..k {
+            power *= p;
+            divisors.extend(smaller.iter().map(|d| d * power));
+        }
+    }
+
+    divisors.sort();
+    divisors
+}
+
+/// σ(n), the sum of every divisor of `n`, or `None` if it doesn't fit in a `u64`
+pub fn sigma(n: u64) -> Option<u64> {
+    factorize(n).into_iter().try_fold(1u64, |total, (p, k)| {
+        // 1 + p + p² + ... + p^k
+        let powers = (0..k).try_fold(1u64, |acc, _| acc.checked_mul(p)?.checked_add(1))?;
+        total.checked_mul(powers)
+    })
+}
+
+/// τ(n), the number of divisors of `n`
+pub fn tau(n: u64) -> u64 {
+    factorize(n)
+        .into_iter()
+        .map(|(_, k)| k as u64 + 1)
+        .product()
+}
+
+/// σ for every number up to and including `limit`, which is much quicker than factorising them one at a time
+pub fn divisor_sums(limit: usize) -> Vec<u64> {
+    divisor_sums_limited(limit, usize::MAX)
+}
+
+/// Like `divisor_sums`, but each divisor only counts towards its first `multiples` multiples
+pub fn divisor_sums_limited(limit: usize, multiples: usize) -> Vec<u64> {
+    let mut sums = vec![0; limit + 1];
+    for d in 1..=limit {
+        let last = limit.min(d.saturating_mul(multiples));
+        for n in (d..=last).step_by(d) {
+            sums[n] += d as u64;
+        }
+    }
+    sums
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        divisor_sums, divisor_sums_limited, divisors, factorize, is_prime, primes_in, primes_up_to,
+        sigma, tau,
+    };
+
+    #[test]
+    fn sieve() {
+        assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_up_to(1), []);
+
+        // this spans a few segments, and should agree with testing each number on its own
+        let range = 1_000_000_000..=1_000_200_000;
+        let primes = primes_in(range.clone());
+        assert_eq!(primes, range.filter(|&n| is_prime(n)).collect::<Vec<_>>());
+        assert_eq!(primes.first(), Some(&1_000_000_007));
+    }
+
+    #[test]
+    fn primality() {
+        assert!(is_prime(2));
+        assert!(!is_prime(1));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // strong pseudoprimes to a lot of small bases
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(3_825_123_056_546_413_051));
+    }
+
+    #[test]
+    fn factors() {
+        assert_eq!(factorize(1), []);
+        assert_eq!(factorize(360), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(
+            factorize(1_000_000_007 * 998_244_353),
+            [(998_244_353, 1), (1_000_000_007, 1)]
+        );
+        assert_eq!(
+            factorize(u64::MAX),
+            [
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6_700_417, 1)
+            ]
+        );
+
+        assert_eq!(divisors(28), [1, 2, 4, 7, 14, 28]);
+        assert_eq!(sigma(28), Some(56));
+        assert_eq!(tau(360), 24);
+        assert_eq!(sigma(1), Some(1));
+        assert_eq!(
+            sigma(18_446_744_073_709_551_557),
+            Some(18_446_744_073_709_551_558)
+        );
+        assert_eq!(sigma(u64::MAX), None);
+        assert_eq!(sigma(1 << 63), Some(u64::MAX));
+
+        let sums = divisor_sums(100);
+        assert!((1..=100).all(|n| Some(sums[n]) == sigma(n as u64)));
+        assert_eq!(divisor_sums_limited(12, 2)[12], 12 + 6);
+    }
+}
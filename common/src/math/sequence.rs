@@ -0,0 +1,140 @@
+use crate::linalg::Rational;
+
+/// The rows of finite differences, starting with the values themselves and stopping at the first row that's all
+/// the same (or that only has one value left)
+pub fn differences(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut rows = vec![values.to_vec()];
+    while let Some(row) = rows.last() {
+        if row.len() < 2 || row.iter().all(|x| *x == row[0]) {
+            break;
+        }
+        rows.push(row.windows(2).map(|x| x[1] - x[0]).collect());
+    }
+    rows
+}
+
+/// The degree of the simplest polynomial that fits the values, if there are enough values to be sure. The last row
+/// of differences has to have at least two values that agree, otherwise anything would fit.
+pub fn degree(values: &[i64]) -> Option<usize> {
+    let rows = differences(values);
+    let last = rows.last()?;
+    (last.len() >= 2).then_some(rows.len() - 1)
+}
+
+/// The `n`th value of the simplest polynomial through the values, where the values are at `0, 1, 2...`. `n` can be
+/// past either end, so `-1` is the value before the first one. This is Newton's forward difference formula, so it
+/// stays in integers the whole way.
+pub fn nth(values: &[i64], n: i64) -> i64 {
+    assert!(!values.is_empty(), "can't extrapolate from nothing");
+    let n = n as i128;
+
+    // the sum of choose(n, k) times the first difference on each row
+    let mut total = 0i128;
+    let mut choose = 1i128;
+    for (k, row) in differences(values).iter().enumerate() {
+        let k = k as i128;
+        total += choose * row[0] as i128;
+        choose = choose * (n - k) / (k + 1);
+    }
+
+    total
+        .try_into()
+        .expect("extrapolated value doesn't fit in an i64")
+}
+
+/// The value after the last one
+pub fn extrapolate(values: &[i64]) -> i64 {
+    nth(values, values.len() as i64)
+}
+
+/// The value before the first one
+pub fn extrapolate_back(values: &[i64]) -> i64 {
+    nth(values, -1)
+}
+
+// https://en.wikipedia.org/wiki/Lagrange_polynomial
+/// The value at `x` of the polynomial through all of the points, as an exact fraction. The points don't have to be
+/// evenly spaced, but they do need different `x`s.
+pub fn interpolate<T: Copy + Into<Rational>>(points: &[(T, T)], x: T) -> Rational {
+    let x: Rational = x.into();
+    let points: Vec<(Rational, Rational)> = points
+        .iter()
+        .map(|&(px, py)| (px.into(), py.into()))
+        .collect();
+
+    points
+        .iter()
+        .enumerate()
+        .fold(Rational::ZERO, |acc, (i, (xi, yi))| {
+            let basis = points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(Rational::ONE, |acc, (_, (xj, _))| {
+                    acc * ((&x - xj) / (xi - xj))
+                });
+            acc + basis * yi
+        })
+}
+
+/// For something that grows like a polynomial every `period` steps, like a pattern repeating across an infinite
+/// grid: take `degree + 1` samples from `series` at `offset`, `offset + period`... and extrapolate them out to the
+/// value at `target`, which has to be one of those steps too.
+pub fn sample_and_extrapolate(
+    series: impl IntoIterator<Item = i64>,
+    offset: usize,
+    period: usize,
+    degree: usize,
+    target: usize,
+) -> i64 {
+    assert!(
+        target >= offset && (target - offset).is_multiple_of(period),
+        "{target} isn't {offset} plus a multiple of {period}"
+    );
+
+    let samples: Vec<i64> = series
+        .into_iter()
+        .skip(offset)
+        .step_by(period)
+        .take(degree + 1)
+        .collect();
+    assert_eq!(samples.len(), degree + 1, "the series ended too soon");
+
+    nth(&samples, ((target - offset) / period) as i64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{degree, extrapolate, extrapolate_back, interpolate, nth, sample_and_extrapolate};
+    use crate::linalg::Rational;
+
+    #[test]
+    fn finite_differences() {
+        let squares = [0, 1, 4, 9, 16];
+        assert_eq!(degree(&squares), Some(2));
+        assert_eq!(extrapolate(&squares), 25);
+        assert_eq!(extrapolate_back(&squares), 1);
+        assert_eq!(nth(&squares, 1000), 1_000_000);
+
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(extrapolate_back(&[10, 13, 16, 21, 30, 45]), 5);
+        assert_eq!(degree(&[7, 7]), Some(0));
+        assert_eq!(degree(&[1, 2, 4]), None);
+    }
+
+    #[test]
+    fn lagrange() {
+        // y = x² / 2, which only hits whole numbers at even x
+        let points = [(0, 0), (2, 2), (4, 8)];
+        assert_eq!(interpolate(&points, 3), Rational::new(9, 2));
+        assert_eq!(interpolate(&points, -2), 2.into());
+    }
+
+    #[test]
+    fn sampling() {
+        // n² plus some wobble that repeats every 4 steps
+        let series = (0..).map(|n: i64| n * n + [0, 3, 1, 2][n as usize % 4]);
+        let expected = 1001 * 1001 + 3;
+        assert_eq!(sample_and_extrapolate(series, 1, 4, 2, 1001), expected);
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    cell::Cell,
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A cache for recursive solvers. `get_or_compute` hands the cache back to the closure, so the computation can
+/// recurse through the same cache without fighting the borrow checker.
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    // insertion order, only tracked when there's a limit so we know what to evict
+    order: VecDeque<K>,
+    limit: Option<usize>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            limit: None,
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache that holds at most `limit` entries, evicting the oldest ones first
+    pub fn with_limit(limit: usize) -> Self {
+        Memo {
+            limit: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    pub fn get_or_compute<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Look up a cached value, counting it as a hit or a miss
+    pub fn get(&self, key: &K) -> Option<V> {
+        let value = self.cache.get(key).cloned();
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.set(counter.get() + 1);
+
+        value
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if let Some(limit) = self.limit {
+            if !self.cache.contains_key(&key) {
+                while self.cache.len() >= limit {
+                    let Some(oldest) = self.order.pop_front() else {
+                        // a limit of zero means nothing ever gets cached
+                        return;
+                    };
+                    self.cache.remove(&oldest);
+                }
+                self.order.push_back(key.clone());
+            }
+        }
+
+        self.cache.insert(key, value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Empty the cache and reset the statistics
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.order.clear();
+        self.hits.set(0);
+        self.misses.set(0);
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses.get()
+    }
+}
+
+/// Wrap a pure function with a cache keyed on its arguments. The arguments need to be owned, `Clone + Eq + Hash`
+/// values, and the cache lives for the rest of the program (one per thread), so the result can only depend on
+/// the arguments.
+#[macro_export]
+macro_rules! memoize {
+    ($(#[$attr:meta])* $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $body:block) => {
+        $(#[$attr])*
+        $vis fn $name($($arg: $ty),*) -> $ret {
+            std::thread_local! {
+                static CACHE: std::cell::RefCell<$crate::memo::Memo<($($ty,)*), $ret>> =
+                    std::cell::RefCell::new($crate::memo::Memo::new());
+            }
+
+            let key = ($($arg.clone(),)*);
+            if let Some(value) = CACHE.with_borrow(|cache| cache.get(&key)) {
+                return value;
+            }
+
+            // the cache isn't borrowed while we compute, so the body is free to call back into this function
+            fn compute($($arg: $ty),*) -> $ret $body
+            let value = compute($($arg),*);
+            CACHE.with_borrow_mut(|cache| cache.insert(key, value.clone()));
+            value
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::Memo;
+
+    fn collatz(memo: &mut Memo<u64, u32>, n: u64) -> u32 {
+        memo.get_or_compute(n, |memo| match n {
+            1 => 0,
+            n if n % 2 == 0 => 1 + collatz(memo, n / 2),
+            n => 1 + collatz(memo, 3 * n + 1),
+        })
+    }
+
+    #[test]
+    fn recursive() {
+        let mut memo = Memo::new();
+        assert_eq!(collatz(&mut memo, 27), 111);
+        assert_eq!(memo.len(), 112);
+        assert_eq!(memo.hits(), 0);
+
+        // 82 is the first step after 27, so it's already cached
+        assert_eq!(collatz(&mut memo, 82), 110);
+        assert_eq!(memo.hits(), 1);
+        assert_eq!(memo.misses(), 112);
+    }
+
+    #[test]
+    fn limited() {
+        let mut memo = Memo::with_limit(10);
+        assert_eq!(collatz(&mut memo, 27), 111);
+        assert_eq!(memo.len(), 10);
+
+        // the most recent entries are still around
+        assert_eq!(collatz(&mut memo, 82), 110);
+        assert_eq!(memo.hits(), 1);
+
+        // but the oldest ones were evicted, so this gets computed all over again
+        assert_eq!(collatz(&mut memo, 2), 1);
+        assert_eq!(memo.hits(), 1);
+    }
+
+    crate::memoize! {
+        fn paths(width: usize, height: usize) -> u64 {
+            if width == 0 || height == 0 {
+                1
+            } else {
+                paths(width - 1, height) + paths(width, height - 1)
+            }
+        }
+    }
+
+    #[test]
+    fn macro_cache() {
+        assert_eq!(paths(2, 2), 6);
+        assert_eq!(paths(16, 16), 601080390);
+    }
+}
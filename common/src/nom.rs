@@ -1,10 +1,15 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
 use nom::{
+    branch::alt,
     bytes::complete::{tag, take},
-    character::complete::{anychar, newline, u32},
-    combinator::{map, map_opt},
-    error::ParseError,
+    character::complete::{
+        alphanumeric1, anychar, char, i32, i64, newline, not_line_ending, space0, space1, u32, u64,
+    },
+    combinator::{map, map_opt, opt},
+    error::{ErrorKind, ParseError},
     multi::{many1, many_till, separated_list1},
-    sequence::separated_pair,
+    sequence::{delimited, preceded, separated_pair, terminated},
     IResult, Input, Parser,
 };
 
@@ -21,6 +26,113 @@ where
     map(separated_list1(newline, many1(f)), Grid::new)
 }
 
+/// A character in a grid that marks a position, like the `S` for the start, rather than being a tile itself.
+/// Wherever it appears, the grid gets `replacement` instead.
+#[derive(Debug, Clone, Copy)]
+pub struct Marker<T> {
+    symbol: char,
+    replacement: T,
+    unique: bool,
+}
+
+impl<T> Marker<T> {
+    /// A marker that has to be in the grid exactly once
+    pub fn unique(symbol: char, replacement: T) -> Self {
+        Marker {
+            symbol,
+            replacement,
+            unique: true,
+        }
+    }
+
+    /// A marker that can be in the grid any number of times, including none
+    pub fn many(symbol: char, replacement: T) -> Self {
+        Marker {
+            symbol,
+            replacement,
+            unique: false,
+        }
+    }
+}
+
+/// Where each marker was found by `parse_grid_with_markers`, in reading order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers {
+    positions: BTreeMap<char, Vec<Coord>>,
+}
+
+impl Markers {
+    /// The position of a marker that's only in the grid once
+    pub fn get(&self, symbol: char) -> Coord {
+        match self.all(symbol) {
+            &[position] => position,
+            all => panic!("expected one {symbol} but found {}", all.len()),
+        }
+    }
+
+    pub fn all(&self, symbol: char) -> &[Coord] {
+        self.positions.get(&symbol).map_or(&[], Vec::as_slice)
+    }
+}
+
+enum Cell<T> {
+    Marker(usize),
+    Tile(T),
+}
+
+/// Like `parse_grid`, but the marker characters are taken out of the grid and their positions are kept instead.
+/// Fails (with `ErrorKind::Verify`) if a unique marker is missing or there's more than one of it.
+pub fn parse_grid_with_markers<'a, T, F>(
+    markers: impl IntoIterator<Item = Marker<T>>,
+    mut tile: F,
+) -> impl Parser<&'a str, Output = (Grid<T>, Markers), Error = nom::error::Error<&'a str>>
+where
+    T: Copy,
+    F: Parser<&'a str, Output = T, Error = nom::error::Error<&'a str>>,
+{
+    let markers: Vec<Marker<T>> = markers.into_iter().collect();
+
+    move |s: &'a str| {
+        let marker = map_opt(anychar, |c| markers.iter().position(|m| m.symbol == c));
+        let cell = alt((
+            map(marker, Cell::Marker),
+            map(|s| tile.parse(s), Cell::Tile),
+        ));
+        let (rest, rows) = separated_list1(newline, many1(cell)).parse(s)?;
+
+        let mut positions: BTreeMap<char, Vec<Coord>> =
+            markers.iter().map(|m| (m.symbol, vec![])).collect();
+        let points = rows
+            .into_iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(x, cell)| match cell {
+                        Cell::Tile(tile) => tile,
+                        Cell::Marker(i) => {
+                            positions.entry(markers[i].symbol).or_default().push((x, y));
+                            markers[i].replacement
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if markers
+            .iter()
+            .any(|m| m.unique && positions[&m.symbol].len() != 1)
+        {
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                s,
+                ErrorKind::Verify,
+            )));
+        }
+
+        Ok((rest, (Grid::new(points), Markers { positions })))
+    }
+}
+
 pub fn drop_till<I, O, E: ParseError<I>, F>(parser: F) -> impl Parser<I, Output = O, Error = E>
 where
     I: Clone + Input,
@@ -47,3 +159,412 @@ pub fn coord(s: &str) -> IResult<&str, Coord> {
 pub fn usize(s: &str) -> IResult<&str, usize> {
     map(u32, |x| x as usize).parse(s)
 }
+
+/// The integer types the number parsers below can produce, so each of them works for `i64`, `u64`, `usize`...
+pub trait Number: Sized {
+    fn parse<'a, E: ParseError<&'a str>>(s: &'a str) -> IResult<&'a str, Self, E>;
+}
+
+macro_rules! number {
+    ($($t:ident)*) => {$(
+        impl Number for $t {
+            fn parse<'a, E: ParseError<&'a str>>(s: &'a str) -> IResult<&'a str, Self, E> {
+                $t(s)
+            }
+        }
+    )*};
+}
+
+number!(i32 i64 u32 u64);
+
+impl Number for usize {
+    fn parse<'a, E: ParseError<&'a str>>(s: &'a str) -> IResult<&'a str, Self, E> {
+        map(u64, |x| x as usize).parse(s)
+    }
+}
+
+pub fn number<'a, T: Number, E: ParseError<&'a str>>(s: &'a str) -> IResult<&'a str, T, E> {
+    T::parse(s)
+}
+
+/// Every integer in the rest of the line, skipping over whatever text is around them. Unsigned types skip the `-`
+/// along with everything else, so `10-20` is `[10, -20]` as `i64` but `[10, 20]` as `u64`. A run of digits that
+/// doesn't fit in a `T` is a failure (`ErrorKind::TooLarge`), rather than being split into smaller numbers.
+pub fn integers<'a, T: Number, E: ParseError<&'a str>>(s: &'a str) -> IResult<&'a str, Vec<T>, E> {
+    let (rest, mut line) = not_line_ending(s)?;
+
+    let mut values = vec![];
+    while let Some(start) = line.find(|c: char| c.is_ascii_digit()) {
+        let end = line[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(line.len(), |len| start + len);
+
+        // try it with the minus sign first, which only signed types will take
+        let signed =
+            (start > 0 && line.as_bytes()[start - 1] == b'-').then(|| &line[start - 1..end]);
+        let value = signed
+            .into_iter()
+            .chain([&line[start..end]])
+            .find_map(|digits| number::<T, ()>(digits).ok().map(|(_, value)| value))
+            .ok_or_else(|| {
+                nom::Err::Failure(E::from_error_kind(&line[start..], ErrorKind::TooLarge))
+            })?;
+
+        values.push(value);
+        line = &line[end..];
+    }
+
+    Ok((rest, values))
+}
+
+/// A line of numbers separated by spaces, commas, or both
+pub fn numbers<'a, T: Number, E: ParseError<&'a str>>(s: &'a str) -> IResult<&'a str, Vec<T>, E> {
+    separated_list1(alt((delimited(space0, tag(","), space0), space1)), T::parse).parse(s)
+}
+
+/// One thing per line
+pub fn lines<'a, O, E, F>(f: F) -> impl Parser<&'a str, Output = Vec<O>, Error = E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, Output = O, Error = E>,
+{
+    separated_list1(newline, f)
+}
+
+/// Chunks of lines with a blank line between each one
+pub fn blocks<'a, O, E, F>(f: F) -> impl Parser<&'a str, Output = Vec<O>, Error = E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, Output = O, Error = E>,
+{
+    separated_list1(tag("\n\n"), f)
+}
+
+/// Things separated by commas, with or without spaces around them
+pub fn comma_list<'a, O, E, F>(f: F) -> impl Parser<&'a str, Output = Vec<O>, Error = E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, Output = O, Error = E>,
+{
+    separated_list1(delimited(space0, char(','), space0), f)
+}
+
+/// `key: value`
+pub fn key_value<'a, K, V, E, F, G>(
+    key: F,
+    value: G,
+) -> impl Parser<&'a str, Output = (K, V), Error = E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, Output = K, Error = E>,
+    G: Parser<&'a str, Output = V, Error = E>,
+{
+    separated_pair(key, (char(':'), space0), value)
+}
+
+/// A line of an adjacency list like `a -> b, c`, where `arrow` is whatever goes between the node and its neighbours
+pub fn adjacency<'a, O, E, F>(
+    arrow: &'a str,
+    node: F,
+) -> impl Parser<&'a str, Output = (O, Vec<O>), Error = E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, Output = O, Error = E> + Clone,
+{
+    separated_pair(
+        node.clone(),
+        delimited(space0, tag(arrow), space0),
+        comma_list(node),
+    )
+}
+
+/// A labelled tuple like `p=<1,-2,3>` or `position=< 9,  1>`
+pub fn labelled_tuple<'a, T: Number, const N: usize, E: ParseError<&'a str>>(
+    label: &'a str,
+) -> impl Parser<&'a str, Output = [T; N], Error = E> {
+    map_opt(
+        preceded(
+            (tag(label), char('='), char('<'), space0),
+            terminated(comma_list(T::parse), (space0, char('>'))),
+        ),
+        |values| values.try_into().ok(),
+    )
+}
+
+/// A labelled range like `x=1..3`, or a single value like `x=5`
+pub fn labelled_range<'a, T: Number + Copy, E: ParseError<&'a str>>(
+    label: &'a str,
+) -> impl Parser<&'a str, Output = RangeInclusive<T>, Error = E> {
+    map(
+        preceded(
+            (tag(label), char('=')),
+            (T::parse, opt(preceded(tag(".."), T::parse))),
+        ),
+        |(start, end)| start..=end.unwrap_or(start),
+    )
+}
+
+/// Something that can be parsed off the front of the input. Usually this is derived from a format string with
+/// `#[derive(AocParse)]`, where each field in the format is parsed with its own type's `AocParse`.
+pub trait AocParse: Sized {
+    fn parse(s: &str) -> IResult<&str, Self>;
+}
+
+pub use common_derive::AocParse;
+
+impl<T: Number> AocParse for T {
+    fn parse(s: &str) -> IResult<&str, Self> {
+        number(s)
+    }
+}
+
+impl AocParse for char {
+    fn parse(s: &str) -> IResult<&str, Self> {
+        anychar(s)
+    }
+}
+
+/// A single word of letters and digits
+impl AocParse for String {
+    fn parse(s: &str) -> IResult<&str, Self> {
+        map(alphanumeric1, String::from).parse(s)
+    }
+}
+
+impl<T: AocParse> AocParse for Vec<T> {
+    fn parse(s: &str) -> IResult<&str, Self> {
+        comma_list(T::parse).parse(s)
+    }
+}
+
+// tuples are comma separated, the same as lists
+impl<A: AocParse, B: AocParse> AocParse for (A, B) {
+    fn parse(s: &str) -> IResult<&str, Self> {
+        separated_pair(A::parse, comma, B::parse).parse(s)
+    }
+}
+
+impl<A: AocParse, B: AocParse, C: AocParse> AocParse for (A, B, C) {
+    fn parse(s: &str) -> IResult<&str, Self> {
+        (
+            terminated(A::parse, comma),
+            terminated(B::parse, comma),
+            C::parse,
+        )
+            .parse(s)
+    }
+}
+
+fn comma(s: &str) -> IResult<&str, char> {
+    delimited(space0, char(','), space0).parse(s)
+}
+
+// what the derived parsers are built out of
+#[doc(hidden)]
+pub mod __private {
+    use nom::{
+        bytes::complete::tag,
+        error::{Error, ErrorKind},
+        Err, Parser,
+    };
+
+    pub use nom::IResult;
+
+    pub type Alternative<T> = fn(&str) -> IResult<&str, T>;
+
+    pub fn literal<'a>(s: &'a str, text: &str) -> IResult<&'a str, &'a str> {
+        tag(text).parse(s)
+    }
+
+    /// The result of the first parser that matches, for an enum with a format on each variant
+    pub fn first_of<'a, T>(s: &'a str, parsers: &[Alternative<T>]) -> IResult<&'a str, T> {
+        for parser in parsers {
+            match parser(s) {
+                Err(Err::Error(_)) => continue,
+                result => return result,
+            }
+        }
+
+        Err(Err::Error(Error::new(s, ErrorKind::Alt)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nom::{
+        character::complete::{alpha1, one_of},
+        Parser,
+    };
+
+    use super::{
+        adjacency, blocks, integers, key_value, labelled_range, labelled_tuple, lines, number,
+        numbers, parse_grid_with_markers, AocParse, Marker,
+    };
+
+    type E<'a> = nom::error::Error<&'a str>;
+
+    #[test]
+    fn numbers_anywhere() {
+        let line = "Sensor at x=-2, y=15: closest beacon is at x=10-20\nnext";
+        assert_eq!(
+            integers::<i64, E>(line).unwrap(),
+            ("\nnext", vec![-2, 15, 10, -20])
+        );
+        assert_eq!(integers::<u64, E>(line).unwrap().1, [2, 15, 10, 20]);
+        assert_eq!(integers::<usize, E>("no numbers").unwrap().1, []);
+
+        // too big for the type, so it's an error rather than a different number
+        let big = "x=-9223372036854775808 y=123456789012345678901";
+        assert_eq!(integers::<i64, E>(&big[..22]).unwrap().1, [i64::MIN]);
+        assert_eq!(
+            integers::<u64, E>(big),
+            Err(nom::Err::Failure(nom::error::Error::new(
+                "123456789012345678901",
+                nom::error::ErrorKind::TooLarge
+            )))
+        );
+        assert!(integers::<i64, E>("x=9223372036854775808").is_err());
+
+        assert_eq!(numbers::<i64, E>("1 -2,3 ,  4").unwrap().1, [1, -2, 3, 4]);
+        assert_eq!(
+            lines(numbers::<usize, E>).parse("1 2\n3 4").unwrap().1,
+            [[1, 2], [3, 4]]
+        );
+        assert_eq!(
+            blocks(lines(number::<u64, E>))
+                .parse("1\n2\n\n3")
+                .unwrap()
+                .1,
+            [vec![1, 2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn shapes() {
+        assert_eq!(
+            key_value(alpha1, number::<u64, E>)
+                .parse("Cost: 12")
+                .unwrap()
+                .1,
+            ("Cost", 12)
+        );
+        assert_eq!(
+            adjacency("<->", number::<usize, E>)
+                .parse("2 <-> 0, 3,4")
+                .unwrap()
+                .1,
+            (2, vec![0, 3, 4])
+        );
+        assert_eq!(
+            labelled_tuple::<i64, 3, E>("p")
+                .parse("p=< 3,-1, 0>")
+                .unwrap()
+                .1,
+            [3, -1, 0]
+        );
+        assert!(labelled_tuple::<i64, 2, E>("p")
+            .parse("p=<3,-1,0>")
+            .is_err());
+        assert_eq!(
+            labelled_range::<i64, E>("x").parse("x=-5..3").unwrap().1,
+            -5..=3
+        );
+        assert_eq!(
+            labelled_range::<usize, E>("y").parse("y=7").unwrap().1,
+            7..=7
+        );
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")]
+    struct Sensor {
+        sx: i64,
+        sy: i64,
+        bx: i64,
+        by: i64,
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc("pos=<{0}>, r={1}")]
+    struct Nanobot((i64, i64, i64), u64);
+
+    #[derive(Debug, PartialEq, AocParse)]
+    enum Instruction {
+        #[aoc("noop")]
+        Noop,
+        #[aoc("addx {0}")]
+        Add(i32),
+        #[aoc("{{{name}}} -> {targets}")]
+        Send { name: String, targets: Vec<usize> },
+    }
+
+    #[test]
+    fn derived() {
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        assert_eq!(
+            Sensor::parse(line).unwrap(),
+            (
+                "",
+                Sensor {
+                    sx: 2,
+                    sy: 18,
+                    bx: -2,
+                    by: 15
+                }
+            )
+        );
+        assert_eq!(
+            Nanobot::parse("pos=<0,-1,2>, r=4").unwrap().1,
+            Nanobot((0, -1, 2), 4)
+        );
+
+        let program = lines(Instruction::parse)
+            .parse("addx -3\nnoop\n{ab1} -> 1, 2")
+            .unwrap()
+            .1;
+        assert_eq!(
+            program,
+            [
+                Instruction::Add(-3),
+                Instruction::Noop,
+                Instruction::Send {
+                    name: "ab1".to_string(),
+                    targets: vec![1, 2]
+                }
+            ]
+        );
+        assert!(Instruction::parse("addx").is_err());
+    }
+
+    #[test]
+    fn markers() {
+        let markers = || {
+            [
+                Marker::unique('S', '.'),
+                Marker::unique('E', '.'),
+                Marker::many('o', '#'),
+            ]
+        };
+
+        let (grid, found) = parse_grid_with_markers(markers(), one_of(".#"))
+            .parse("S.o\n#o.\n..E")
+            .unwrap()
+            .1;
+        assert_eq!(
+            grid.points,
+            [['.', '.', '#'], ['#', '#', '.'], ['.', '.', '.']]
+        );
+        assert_eq!(found.get('S'), (0, 0));
+        assert_eq!(found.get('E'), (2, 2));
+        assert_eq!(found.all('o'), [(2, 0), (1, 1)]);
+        assert_eq!(found.all('x'), []);
+
+        // the start is missing, then there are two ends
+        assert!(parse_grid_with_markers(markers(), one_of(".#"))
+            .parse(".o.\n..E")
+            .is_err());
+        assert!(parse_grid_with_markers(markers(), one_of(".#"))
+            .parse("S.E\n..E")
+            .is_err());
+    }
+}
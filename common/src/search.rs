@@ -0,0 +1,482 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+const NO_PARENT: usize = usize::MAX;
+
+/// Every state we've discovered, along with the index of the state we reached it from and the cost to get there
+struct Visited<S, C> {
+    index: HashMap<S, usize>,
+    nodes: Vec<(S, usize, C)>,
+}
+
+impl<S, C> Visited<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord,
+{
+    fn new(start: S, cost: C) -> Self {
+        Visited {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start, NO_PARENT, cost)],
+        }
+    }
+
+    fn state(&self, i: usize) -> &S {
+        &self.nodes[i].0
+    }
+
+    fn cost(&self, i: usize) -> C {
+        self.nodes[i].2
+    }
+
+    /// Record that `state` can be reached from `parent` for `cost`. Returns the index of the state if this is
+    /// the first time we've seen it or if it's cheaper than the last way we found to get there.
+    fn relax(&mut self, state: S, parent: usize, cost: C) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Vacant(e) => {
+                let i = self.nodes.len();
+                self.nodes.push((e.key().clone(), parent, cost));
+                e.insert(i);
+                Some(i)
+            }
+            Entry::Occupied(e) => {
+                let i = *e.get();
+                let node = &mut self.nodes[i];
+                (cost < node.2).then(|| {
+                    node.1 = parent;
+                    node.2 = cost;
+                    i
+                })
+            }
+        }
+    }
+
+    /// Walk the parent pointers back to the start
+    fn path(&self, mut i: usize) -> Vec<S> {
+        let mut path = vec![];
+        while i != NO_PARENT {
+            let (state, parent, _) = &self.nodes[i];
+            path.push(state.clone());
+            i = *parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth first search where every step costs 1. Returns the number of steps taken and the path from `start` to
+/// the first state that satisfies `success` (both inclusive).
+pub fn bfs<S, FN, IN, FG>(start: S, mut successors: FN, mut success: FG) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut visited = Visited::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        if success(visited.state(i)) {
+            return Some((visited.cost(i), visited.path(i)));
+        }
+
+        let steps = visited.cost(i) + 1;
+        for next in successors(visited.state(i)) {
+            // the first time we see a state in a bfs is always the shortest, so relax only succeeds for new states
+            if let Some(j) = visited.relax(next, i, steps) {
+                queue.push_back(j);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm over states produced by `successors`, which yields each neighbor along with the cost to
+/// move there. Returns the total cost and the path from `start` to the first state that satisfies `success`.
+pub fn dijkstra<S, C, FN, IN, FG>(start: S, successors: FN, success: FG) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// A* search. The `heuristic` must never overestimate the remaining cost to a goal or the path may not be optimal.
+pub fn astar<S, C, FN, IN, FH, FG>(
+    start: S,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FG,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let zero = C::default();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut visited = Visited::new(start, zero);
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // we've already found a cheaper way here since this was queued
+        if cost > visited.cost(i) {
+            continue;
+        }
+
+        if success(visited.state(i)) {
+            return Some((cost, visited.path(i)));
+        }
+
+        for (next, step) in successors(visited.state(i)) {
+            let next_cost = cost + step;
+            if let Some(j) = visited.relax(next, i, next_cost) {
+                let estimate = next_cost + heuristic(visited.state(j));
+                heap.push(Reverse((estimate, next_cost, j)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every optimal path from the start to the cheapest goal(s), found by [`dijkstra_all`]. Each state keeps all of
+/// the predecessors that reach it for its optimal cost, so the paths form a directed acyclic graph.
+pub struct ShortestPathDag<S, C> {
+    states: Vec<S>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+    cost: C,
+}
+
+impl<S, C> ShortestPathDag<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    /// The cost of every optimal path
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// All of the goal states that can be reached for the optimal cost
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.states[i])
+    }
+
+    /// The number of distinct optimal paths, or `None` if there are more than fit in a `u64`
+    pub fn count_paths(&self) -> Option<u64> {
+        // states are stored in the order they were settled, so every predecessor comes before its successors
+        let mut counts = vec![0u64; self.states.len()];
+        counts[0] = 1;
+        for i in 1..self.states.len() {
+            counts[i] = self.predecessors[i]
+                .iter()
+                .try_fold(0u64, |acc, &p| acc.checked_add(counts[p]))?;
+        }
+
+        self.goals
+            .iter()
+            .try_fold(0u64, |acc, &g| acc.checked_add(counts[g]))
+    }
+
+    /// Lazily enumerate every optimal path from the start to a goal
+    pub fn paths(&self) -> Paths<'_, S, C> {
+        Paths {
+            dag: self,
+            goals: self.goals.iter(),
+            stack: vec![],
+        }
+    }
+
+    /// Every state that lies on at least one optimal path
+    pub fn nodes(&self) -> HashSet<S> {
+        self.on_path().map(|i| self.states[i].clone()).collect()
+    }
+
+    /// Every `(from, to)` step that is part of at least one optimal path
+    pub fn edges(&self) -> HashSet<(S, S)> {
+        self.on_path()
+            .flat_map(|i| {
+                self.predecessors[i]
+                    .iter()
+                    .map(move |&p| (self.states[p].clone(), self.states[i].clone()))
+            })
+            .collect()
+    }
+
+    /// Walk backwards from the goals to find the indices of every state on an optimal path
+    fn on_path(&self) -> impl Iterator<Item = usize> {
+        let mut seen = vec![false; self.states.len()];
+        let mut queue = self.goals.clone();
+        while let Some(i) = queue.pop() {
+            if !seen[i] {
+                seen[i] = true;
+                queue.extend(&self.predecessors[i]);
+            }
+        }
+
+        seen.into_iter()
+            .enumerate()
+            .filter_map(|(i, on_path)| on_path.then_some(i))
+    }
+}
+
+/// Iterator over the optimal paths in a [`ShortestPathDag`]
+pub struct Paths<'a, S, C> {
+    dag: &'a ShortestPathDag<S, C>,
+    goals: std::slice::Iter<'a, usize>,
+    // the path we're currently exploring from the goal back towards the start, along with the next predecessor
+    // to try from each state
+    stack: Vec<(usize, usize)>,
+}
+
+impl<S: Clone, C> Iterator for Paths<'_, S, C> {
+    type Item = Vec<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(&(i, next)) = self.stack.last() else {
+                self.stack.push((*self.goals.next()?, 0));
+                continue;
+            };
+
+            let predecessors = &self.dag.predecessors[i];
+            if i == 0 {
+                // we made it back to the start, so the stack is a complete path
+                let path = self
+                    .stack
+                    .iter()
+                    .rev()
+                    .map(|&(j, _)| self.dag.states[j].clone())
+                    .collect();
+                self.stack.pop();
+                return Some(path);
+            } else if next < predecessors.len() {
+                self.stack.last_mut().unwrap().1 += 1;
+                self.stack.push((predecessors[next], 0));
+            } else {
+                self.stack.pop();
+            }
+        }
+    }
+}
+
+/// Dijkstra's algorithm that keeps every predecessor that reaches a state for its optimal cost instead of just the
+/// first one, so we can answer questions about all of the shortest paths rather than a single one. Every step must
+/// have a positive cost. Returns `None` if no goal can be reached.
+pub fn dijkstra_all<S, C, FN, IN, FG>(
+    start: S,
+    mut successors: FN,
+    mut success: FG,
+) -> Option<ShortestPathDag<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    let zero = C::default();
+    let mut index: HashMap<S, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![start];
+    let mut costs = vec![zero];
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]];
+    let mut settled = vec![false];
+    let mut order = vec![];
+    let mut goals = vec![];
+    let mut best = None;
+
+    let mut heap = BinaryHeap::from([Reverse((zero, 0))]);
+    while let Some(Reverse((cost, i))) = heap.pop() {
+        if settled[i] || cost > costs[i] {
+            continue;
+        }
+
+        // everything left in the queue is worse than the goals we've found
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+
+        settled[i] = true;
+        order.push(i);
+
+        if success(&states[i]) {
+            best = Some(cost);
+            goals.push(i);
+            continue;
+        }
+
+        for (next, step) in successors(&states[i]) {
+            let next_cost = cost + step;
+            match index.entry(next) {
+                Entry::Vacant(e) => {
+                    states.push(e.key().clone());
+                    costs.push(next_cost);
+                    predecessors.push(vec![i]);
+                    settled.push(false);
+                    e.insert(states.len() - 1);
+                    heap.push(Reverse((next_cost, states.len() - 1)));
+                }
+                Entry::Occupied(e) => {
+                    let j = *e.get();
+                    if next_cost < costs[j] {
+                        costs[j] = next_cost;
+                        predecessors[j] = vec![i];
+                        heap.push(Reverse((next_cost, j)));
+                    } else if next_cost == costs[j] && !settled[j] {
+                        predecessors[j].push(i);
+                    }
+                }
+            }
+        }
+    }
+
+    let cost = best?;
+
+    // renumber the settled states so they're in the order we visited them, which keeps the dag topologically sorted
+    let mut renumber = vec![usize::MAX; states.len()];
+    for (new, &old) in order.iter().enumerate() {
+        renumber[old] = new;
+    }
+
+    let mut states: Vec<Option<S>> = states.into_iter().map(Some).collect();
+    Some(ShortestPathDag {
+        states: order.iter().map(|&i| states[i].take().unwrap()).collect(),
+        predecessors: order
+            .iter()
+            .map(|&i| predecessors[i].iter().map(|&p| renumber[p]).collect())
+            .collect(),
+        goals: goals.iter().map(|&g| renumber[g]).collect(),
+        cost,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bfs, dijkstra, dijkstra_all};
+
+    const MAZE: [&str; 5] = ["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"];
+
+    fn open((x, y): (usize, usize)) -> bool {
+        MAZE.get(y)
+            .and_then(|row| row.as_bytes().get(x))
+            .is_some_and(|&c| c != b'#')
+    }
+
+    fn neighbors(&(x, y): &(usize, usize)) -> Vec<(usize, usize)> {
+        [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ]
+        .into_iter()
+        .filter(|&c| open(c))
+        .collect()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let (steps, path) = bfs((0, 0), neighbors, |&c| c == (7, 4)).unwrap();
+        assert_eq!(steps, 15);
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(7, 4)));
+        assert!(path.windows(2).all(|w| neighbors(&w[0]).contains(&w[1])));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(bfs((0, 0), neighbors, |&c| c == (3, 0)), None);
+        assert_eq!(
+            dijkstra(
+                (0, 0),
+                |c| neighbors(c).into_iter().map(|n| (n, 1)),
+                |&c| c == (3, 0)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        // moving down is expensive
+        let successors = |&(x, y): &(usize, usize)| {
+            neighbors(&(x, y))
+                .into_iter()
+                .map(move |(nx, ny)| ((nx, ny), if ny > y { 5u32 } else { 1 }))
+        };
+        let goal = |&c: &(usize, usize)| c == (7, 4);
+
+        let (cost, path) = dijkstra((0, 0), successors, goal).unwrap();
+        assert_eq!(cost, 39);
+        assert_eq!(path.last(), Some(&(7, 4)));
+
+        let manhattan = |&(x, y): &(usize, usize)| (7 - x + 4 - y) as u32;
+        let (astar_cost, _) = astar((0, 0), successors, manhattan, goal).unwrap();
+        assert_eq!(astar_cost, cost);
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        // every monotone path across a 3x3 grid of points is optimal
+        let successors = |&(x, y): &(u32, u32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 2 && y <= 2)
+                .map(|c| (c, 1))
+        };
+
+        let dag = dijkstra_all((0, 0), successors, |&c| c == (2, 2)).unwrap();
+        assert_eq!(dag.cost(), 4);
+        assert_eq!(dag.count_paths(), Some(6));
+        assert_eq!(dag.paths().count(), 6);
+        assert!(dag.paths().all(|p| p.len() == 5 && p[0] == (0, 0)));
+        assert_eq!(dag.nodes().len(), 9);
+        assert_eq!(dag.edges().len(), 12);
+    }
+
+    #[test]
+    fn all_shortest_paths_ignores_worse_routes() {
+        let (steps, _) = bfs((0, 0), neighbors, |&c| c == (7, 4)).unwrap();
+        let dag = dijkstra_all(
+            (0, 0),
+            |c| neighbors(c).into_iter().map(|n| (n, 1)),
+            |&c| c == (7, 4),
+        )
+        .unwrap();
+
+        assert_eq!(dag.cost(), steps);
+        assert_eq!(dag.count_paths(), Some(1));
+        assert_eq!(dag.nodes().len(), steps + 1);
+        assert_eq!(dag.goals().collect::<Vec<_>>(), vec![&(7, 4)]);
+    }
+
+    #[test]
+    fn path_counts_overflow() {
+        // a chain of diamonds doubles the number of paths at every step
+        let successors = |&n: &u32| match n % 3 {
+            0 => vec![(n + 1, 1), (n + 2, 1)],
+            _ => vec![(n + 3 - n % 3, 1)],
+        };
+
+        let dag = dijkstra_all(0, successors, |&n| n == 3 * 70).unwrap();
+        assert_eq!(dag.count_paths(), None);
+
+        let dag = dijkstra_all(0, successors, |&n| n == 3 * 10).unwrap();
+        assert_eq!(dag.count_paths(), Some(1024));
+    }
+}
@@ -1,35 +1,161 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-pub struct UnionFind(Vec<usize>);
+/// Disjoint sets with path compression and union by rank, so `find` and `union` are effectively constant time
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
 
 impl UnionFind {
     pub fn new(size: usize) -> Self {
-        UnionFind((0..size).collect())
+        UnionFind {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            size: vec![1; size],
+            components: size,
+        }
     }
 
     pub fn find(&mut self, x: usize) -> usize {
-        let mut y = self.0[x];
-        if y != x {
-            y = self.find(y);
+        let root = self.root(x);
+
+        // point everything on the way up directly at the root so the next find is quicker
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
         }
-        y
+
+        root
     }
 
-    pub fn union(&mut self, idx: usize, idy: usize) {
+    /// Merge the sets containing `idx` and `idy`. Returns false if they were already in the same set.
+    pub fn union(&mut self, idx: usize, idy: usize) -> bool {
         let x = self.find(idx);
         let y = self.find(idy);
-        self.0[y] = x;
+        if x == y {
+            return false;
+        }
+
+        // hang the shallower tree off the deeper one so the trees stay flat
+        let (parent, child) = if self.rank[x] < self.rank[y] {
+            (y, x)
+        } else {
+            (x, y)
+        };
+
+        if self.rank[parent] == self.rank[child] {
+            self.rank[parent] += 1;
+        }
+
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, idx: usize, idy: usize) -> bool {
+        self.find(idx) == self.find(idy)
+    }
+
+    /// The number of elements in the set containing `x`
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The number of disjoint sets
+    pub fn len(&self) -> usize {
+        self.components
     }
 
-    pub fn is_empty(&mut self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
-    pub fn len(&mut self) -> usize {
-        let mut s: HashSet<usize> = HashSet::new();
-        for i in 0..self.0.len() {
-            s.insert(self.find(i));
+    /// Every disjoint set, in order of their smallest element
+    pub fn components(&self) -> impl Iterator<Item = Vec<usize>> {
+        let mut order = vec![];
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for x in 0..self.parent.len() {
+            let root = self.root(x);
+            groups
+                .entry(root)
+                .or_insert_with(|| {
+                    order.push(root);
+                    vec![]
+                })
+                .push(x);
+        }
+
+        order
+            .into_iter()
+            .map(move |root| groups.remove(&root).unwrap())
+    }
+
+    /// Walk up to the root without compressing anything along the way
+    fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+}
+
+/// Kruskal's algorithm for the minimum spanning forest of a graph with `nodes` nodes. Returns the edges that were
+/// used, in the order they were added, so the last edge is the one that finally connects everything.
+pub fn kruskal<C: Ord>(
+    nodes: usize,
+    edges: impl IntoIterator<Item = (usize, usize, C)>,
+) -> Vec<(usize, usize, C)> {
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    edges.sort_by(|a, b| a.2.cmp(&b.2));
+
+    let mut uf = UnionFind::new(nodes);
+    let mut tree = vec![];
+    for (a, b, cost) in edges {
+        if uf.union(a, b) {
+            tree.push((a, b, cost));
+            if uf.len() == 1 {
+                break;
+            }
         }
-        s.len()
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod test {
+    use super::{kruskal, UnionFind};
+
+    #[test]
+    fn union_find() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.len(), 6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+
+        assert_eq!(uf.len(), 3);
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+        assert_eq!(uf.size(2), 4);
+        assert_eq!(uf.size(5), 1);
+        assert_eq!(
+            uf.components().collect::<Vec<_>>(),
+            vec![vec![0, 1, 2, 3], vec![4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn minimum_spanning_tree() {
+        let edges = [(0, 1, 4), (0, 2, 1), (1, 2, 2), (1, 3, 5), (2, 3, 8)];
+        let tree = kruskal(4, edges);
+        assert_eq!(tree, vec![(0, 2, 1), (1, 2, 2), (1, 3, 5)]);
     }
 }
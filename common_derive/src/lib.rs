@@ -0,0 +1,190 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// Derive `common::nom::AocParse` from a format string. Structs take the format on the struct and enums take one on
+/// each variant, which are tried in order:
+///
+/// ```ignore
+/// #[derive(AocParse)]
+/// #[aoc("pos=<{position}>, r={radius}")]
+/// struct Nanobot {
+///     position: (i64, i64, i64),
+///     radius: u64,
+/// }
+/// ```
+///
+/// Everything outside of `{field}` has to match exactly, and each field is parsed with its own type's `AocParse`.
+/// Tuple fields go by their index, like `{0}`, and `{{`/`}}` are literal braces.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let format = format(&input.attrs, input)?;
+            constructor(quote!(#name), &data.fields, &format)?
+        }
+        Data::Enum(data) => {
+            let alternatives = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let format = format(&variant.attrs, variant)?;
+                    let ident = &variant.ident;
+                    let parse = constructor(quote!(#name::#ident), &variant.fields, &format)?;
+                    Ok(quote!(|s| { #parse }))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let count = alternatives.len();
+
+            quote! {
+                let alternatives: [::common::nom::__private::Alternative<Self>; #count] =
+                    [#(#alternatives),*];
+                ::common::nom::__private::first_of(s, &alternatives)
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "AocParse can't be derived for unions",
+            ))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::common::nom::AocParse for #name #ty_generics #where_clause {
+            fn parse(s: &str) -> ::common::nom::__private::IResult<&str, Self> {
+                #body
+            }
+        }
+    })
+}
+
+// the format from the #[aoc("...")] on whatever `attrs` belong to
+fn format(attrs: &[Attribute], owner: impl ToTokens) -> Result<LitStr> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("aoc"))
+        .ok_or_else(|| Error::new_spanned(owner, "missing a #[aoc(\"...\")] format"))?
+        .parse_args()
+}
+
+enum Piece {
+    Literal(String),
+    Field(String),
+}
+
+fn pieces(format: &LitStr) -> Result<Vec<Piece>> {
+    let mut pieces = vec![];
+    let mut literal = String::new();
+    let value = format.value();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                literal.push(c);
+                chars.next();
+            }
+            ('{', _) => {
+                let field: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if field.is_empty() || field.contains('{') {
+                    return Err(Error::new_spanned(format, "expected a field name in {}"));
+                }
+
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+                pieces.push(Piece::Field(field.trim().to_string()));
+            }
+            ('}', _) => {
+                return Err(Error::new_spanned(
+                    format,
+                    "unmatched }, use }} for a brace",
+                ))
+            }
+            _ => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+// parse each piece of the format in turn, then build `path` out of the fields
+fn constructor(path: TokenStream2, fields: &Fields, format: &LitStr) -> Result<TokenStream2> {
+    let names: Vec<String> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|f| f.ident.as_ref().unwrap().to_string())
+            .collect(),
+        Fields::Unnamed(unnamed) => (0..unnamed.unnamed.len()).map(|i| i.to_string()).collect(),
+        Fields::Unit => vec![],
+    };
+    let bindings: Vec<_> = (0..names.len())
+        .map(|i| format_ident!("field_{}", i))
+        .collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+
+    let mut seen = vec![false; names.len()];
+    let mut steps = vec![];
+    for piece in pieces(format)? {
+        match piece {
+            Piece::Literal(text) => steps.push(quote! {
+                let (s, _) = ::common::nom::__private::literal(s, #text)?;
+            }),
+            Piece::Field(field) => {
+                let Some(i) = names.iter().position(|name| *name == field) else {
+                    return Err(Error::new_spanned(
+                        format,
+                        format!("no field called `{field}`"),
+                    ));
+                };
+                if std::mem::replace(&mut seen[i], true) {
+                    return Err(Error::new_spanned(
+                        format,
+                        format!("`{field}` is in the format twice"),
+                    ));
+                }
+
+                let (binding, ty) = (&bindings[i], types[i]);
+                steps.push(quote! {
+                    let (s, #binding) = <#ty as ::common::nom::AocParse>::parse(s)?;
+                });
+            }
+        }
+    }
+
+    if let Some(i) = seen.iter().position(|seen| !seen) {
+        return Err(Error::new_spanned(
+            format,
+            format!("`{}` is missing from the format", names[i]),
+        ));
+    }
+
+    let value = match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|f| &f.ident);
+            quote!(#path { #(#idents: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+        Fields::Unit => quote!(#path),
+    };
+
+    Ok(quote! {
+        #(#steps)*
+        Ok((s, #value))
+    })
+}